@@ -0,0 +1,73 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use lib_simulation as sim;
+
+/// Trains birds without rendering anything, as fast as the CPU allows.
+#[derive(Debug, Parser)]
+#[command(name = "shorelark-cli")]
+struct Args {
+    /// JSON file with the simulation config; fields that are missing
+    /// fall back to their defaults
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// How many generations to run
+    #[arg(short, long, default_value_t = 100)]
+    generations: usize,
+
+    /// Seed for the simulation; picked at random when not given
+    #[arg(short, long)]
+    seed: Option<u64>,
+
+    /// Where to write the final population's brains
+    #[arg(short, long, default_value = "population.json")]
+    output: PathBuf,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let config = match &args.config {
+        Some(path) => {
+            let config = fs::read_to_string(path)
+                .with_context(|| format!("couldn't read config from {}", path.display()))?;
+
+            serde_json::from_str(&config)
+                .with_context(|| format!("couldn't parse config from {}", path.display()))?
+        }
+        None => sim::Config::default(),
+    };
+
+    let seed = args.seed.unwrap_or_else(rand::random);
+    let mut simulation = sim::Simulation::from_seed(seed, config)?;
+
+    eprintln!("seed = {seed}");
+    println!("generation\tmin\tmax\tmean\tmedian\tstddev\tfood\tdiversity");
+
+    for _ in 0..args.generations {
+        let stats = simulation.train()?;
+
+        println!(
+            "{}\t{}\t{}\t{:.2}\t{:.1}\t{:.2}\t{}\t{:.3}",
+            stats.generation,
+            stats.min_satiation,
+            stats.max_satiation,
+            stats.mean_satiation,
+            stats.median_satiation,
+            stats.stddev_satiation,
+            stats.food_eaten,
+            stats.diversity,
+        );
+    }
+
+    let population = serde_json::to_string(&simulation.population())?;
+
+    fs::write(&args.output, population)
+        .with_context(|| format!("couldn't write population to {}", args.output.display()))?;
+
+    eprintln!("population written to {}", args.output.display());
+
+    Ok(())
+}
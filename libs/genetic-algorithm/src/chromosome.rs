@@ -1,6 +1,9 @@
 use std::ops::Index;
 
-#[derive(Clone, Debug)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct Chromosome {
     genes: Vec<f32>,
 }
@@ -22,6 +25,28 @@ impl Chromosome {
     pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut f32> {
         self.genes.iter_mut()
     }
+
+    /// Euclidean distance between genes of both chromosomes.
+    ///
+    /// Chromosomes are expected to be of the same length; if they are not,
+    /// the extra genes are ignored.
+    pub fn distance(&self, other: &Chromosome) -> f32 {
+        distance(&self.genes, &other.genes)
+    }
+
+    pub(crate) fn genes_mut(&mut self) -> &mut [f32] {
+        &mut self.genes
+    }
+}
+
+/// Euclidean distance between two points, e.g. chromosomes or behaviours;
+/// extra coordinates of the longer one are ignored.
+pub(crate) fn distance(a: &[f32], b: &[f32]) -> f32 {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| (a - b).powi(2))
+        .sum::<f32>()
+        .sqrt()
 }
 
 impl Index<usize> for Chromosome {
@@ -58,7 +83,7 @@ pub trait Individual {
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
     fn make_chromosome() -> Chromosome {
         Chromosome {
@@ -132,8 +157,22 @@ mod tests {
         assert_eq!(genes[2], 2.0);
     }
 
-    mod histogram {
-        use crate::{selection::{RouletteWheelSelection, SelectionMethod, UniformCrossover}, chromosome::Chromosome, GeneticAlgorithm, mutation::GaussianMutation};
+    #[test]
+    fn distance() {
+        let a: Chromosome = vec![1.0, 2.0, 3.0].into_iter().collect();
+        let b: Chromosome = vec![1.0, 5.0, 7.0].into_iter().collect();
+
+        approx::assert_relative_eq!(a.distance(&b), 5.0);
+        approx::assert_relative_eq!(b.distance(&a), 5.0);
+        approx::assert_relative_eq!(a.distance(&a), 0.0);
+    }
+
+    pub(crate) mod histogram {
+        use crate::{selection::{RouletteWheelSelection, SelectionMethod, UniformCrossover}, chromosome::Chromosome, GeneticAlgorithm, mutation::NormalMutation};
+        use crate::selection::{
+            RankSelection, StochasticUniversalSampling, TournamentSelection, TruncationSelection,
+        };
+        use crate::{MultiObjectiveIndividual, UniformMutation};
 
         use super::*;
         use rand::SeedableRng;
@@ -141,7 +180,7 @@ mod tests {
         use std::collections::BTreeMap;
 
         #[derive(Clone, Debug, PartialEq)]
-        pub enum TestIndividual {
+        pub(crate) enum TestIndividual {
             /// For tests that require access to chromosome
             WithChromosome { chromosome: Chromosome },
 
@@ -184,7 +223,14 @@ mod tests {
             }
         }
 
-        fn individual(genes: &[f32]) -> TestIndividual {
+        /// Each gene is a separate objective.
+        impl MultiObjectiveIndividual for TestIndividual {
+            fn objectives(&self) -> Vec<f32> {
+                self.chromosome().iter().copied().collect()
+            }
+        }
+
+        pub(crate) fn individual(genes: &[f32]) -> TestIndividual {
             let chromosome = genes.iter().cloned().collect();
 
             TestIndividual::create(chromosome)
@@ -203,7 +249,7 @@ mod tests {
             ];
 
             let actual_histogram: BTreeMap<i32, _> = (0..1000)
-                .map(|_| method.select(&mut rng, &population))
+                .map(|_| method.select(&mut rng, &population).unwrap())
                 .fold(Default::default(), |mut histogram, individual| {
                     *histogram.entry(individual.fitness() as _).or_default() += 1;
 
@@ -221,6 +267,143 @@ mod tests {
             assert_eq!(expected_histogram, actual_histogram);
         }
 
+        /// Selects 1000 individuals from the same population the roulette
+        /// wheel test uses and counts how many times each fitness came up.
+        fn histogram(method: &impl SelectionMethod) -> BTreeMap<i32, usize> {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let population = vec![
+                TestIndividual::new(2.0),
+                TestIndividual::new(1.0),
+                TestIndividual::new(4.0),
+                TestIndividual::new(3.0),
+            ];
+
+            method
+                .select_many(&mut rng, &population, 1000)
+                .unwrap()
+                .into_iter()
+                .fold(Default::default(), |mut histogram, individual| {
+                    *histogram.entry(individual.fitness() as _).or_default() += 1;
+
+                    histogram
+                })
+        }
+
+        #[test]
+        fn tournament() {
+            // The better of two random individuals, so the best one wins
+            // (2 * rank - 1) / 16 of the time
+            let expected_histogram = maplit::btreemap! {
+                1 => 75,
+                2 => 177,
+                3 => 300,
+                4 => 448,
+            };
+
+            assert_eq!(
+                expected_histogram,
+                histogram(&TournamentSelection::new(2).unwrap())
+            );
+        }
+
+        #[test]
+        fn tournament_of_one_is_uniform() {
+            let expected_histogram = maplit::btreemap! {
+                1 => 265,
+                2 => 243,
+                3 => 231,
+                4 => 261,
+            };
+
+            assert_eq!(
+                expected_histogram,
+                histogram(&TournamentSelection::new(1).unwrap())
+            );
+        }
+
+        #[test]
+        fn rank() {
+            // Ranks are 1, 2, 3 and 4 - so, coincidentally, roughly the same
+            // proportions as the roulette wheel gets for this population
+            let expected_histogram = maplit::btreemap! {
+                1 => 103,
+                2 => 201,
+                3 => 304,
+                4 => 392,
+            };
+
+            assert_eq!(expected_histogram, histogram(&RankSelection::new()));
+        }
+
+        #[test]
+        fn stochastic_universal_sampling() {
+            // Equally spaced pointers make the histogram exactly
+            // proportional to fitness
+            let expected_histogram = maplit::btreemap! {
+                1 => 100,
+                2 => 200,
+                3 => 300,
+                4 => 400,
+            };
+
+            assert_eq!(
+                expected_histogram,
+                histogram(&StochasticUniversalSampling::new())
+            );
+        }
+
+        #[test]
+        fn truncation() {
+            // Only the top half ever gets picked
+            let expected_histogram = maplit::btreemap! {
+                3 => 490,
+                4 => 510,
+            };
+
+            assert_eq!(
+                expected_histogram,
+                histogram(&TruncationSelection::new(0.5).unwrap())
+            );
+        }
+
+        #[test]
+        fn every_method_handles_all_zero_fitness() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population = vec![TestIndividual::new(0.0), TestIndividual::new(0.0)];
+
+            assert!(RouletteWheelSelection::new()
+                .select(&mut rng, &population)
+                .is_ok());
+            assert!(TournamentSelection::new(3)
+                .unwrap()
+                .select(&mut rng, &population)
+                .is_ok());
+            assert!(RankSelection::new().select(&mut rng, &population).is_ok());
+            assert!(TruncationSelection::new(0.5)
+                .unwrap()
+                .select(&mut rng, &population)
+                .is_ok());
+            assert!(StochasticUniversalSampling::new()
+                .select(&mut rng, &population)
+                .is_ok());
+        }
+
+        #[test]
+        fn invalid_parameters() {
+            assert_eq!(
+                TournamentSelection::new(0).unwrap_err(),
+                crate::Error::InvalidTournamentSize(0),
+            );
+
+            for ratio in [0.0, -0.5, 1.5, f32::NAN] {
+                assert!(matches!(
+                    TruncationSelection::new(ratio),
+                    Err(crate::Error::InvalidTruncationRatio(_)),
+                ));
+            }
+        }
+
         #[test]
         fn test2() {
             let mut rng = ChaCha8Rng::from_seed(Default::default());
@@ -228,7 +411,7 @@ mod tests {
             let ga = GeneticAlgorithm::new(
                 RouletteWheelSelection::new(),
                 UniformCrossover,
-                GaussianMutation::new(0.5, 0.5),
+                UniformMutation::new(0.5, 0.5).unwrap(),
             );
 
             let mut population = vec![
@@ -247,17 +430,148 @@ mod tests {
             // that'd change is the *magnitude* of difference between
             // initial and output population.
             for _ in 0..10 {
-                population = ga.evolve(&mut rng, &population);
+                population = ga.evolve(&mut rng, &population).unwrap();
             }
 
             let expected_population = vec![
-                individual(&[0.44769490, 2.0648358, 4.3058133]), // fitness ~= 6.8
-                individual(&[1.21268670, 1.5538777, 2.8869110]), // fitness ~= 5.7
-                individual(&[1.06176780, 2.2657390, 4.4287640]), // fitness ~= 7.8
-                individual(&[0.95909685, 2.4618788, 4.0247330]), // fitness ~= 7.4
+                individual(&[0.3261536, 1.9858004, 3.4280746]), // fitness ~= 5.7
+                individual(&[1.099327, 2.3394372, 3.7338045]),  // fitness ~= 7.2
+                individual(&[1.593567, 2.54792, 3.7338045]),    // fitness ~= 7.9
+                individual(&[0.5273456, 1.9271864, 4.662364]),  // fitness ~= 7.1
             ];
 
             assert_eq!(expected_population, population);
         }
+
+        pub(crate) fn population(rng: &mut ChaCha8Rng) -> Vec<TestIndividual> {
+            use rand::Rng;
+
+            (0..8)
+                .map(|_| {
+                    let genes: Vec<f32> = (0..3).map(|_| rng.gen_range(-1.0..1.0)).collect();
+                    individual(&genes)
+                })
+                .collect()
+        }
+
+        /// Deliberately not gated on the `parallel` feature: both builds
+        /// have to evolve exactly this population, so running the tests
+        /// with and without the feature (`cargo test --workspace` enables
+        /// it, `cargo test -p lib-genetic-algorithm` doesn't) checks that
+        /// they give the same results for the same seed.
+        #[test]
+        fn same_population_with_and_without_parallel() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut population = population(&mut rng);
+
+            let ga = GeneticAlgorithm::new(
+                TournamentSelection::new(2).unwrap(),
+                UniformCrossover,
+                NormalMutation::new(0.5, 0.5).unwrap(),
+            );
+
+            for _ in 0..3 {
+                population = ga.evolve(&mut rng, &population).unwrap();
+            }
+
+            let expected_population = vec![
+                individual(&[-0.11050708, 0.08921358, 0.81812596]),
+                individual(&[0.11794357, -0.8899413, -0.80786335]),
+                individual(&[-0.22240114, 1.9864427, 0.5552429]),
+                individual(&[-0.23645522, 0.36756086, 0.5492295]),
+                individual(&[1.0247575, -0.31201276, 0.92138356]),
+                individual(&[-0.22240114, -0.7818489, 0.5552429]),
+                individual(&[0.7530371, -0.8899413, 1.3677084]),
+                individual(&[0.069369555, 0.10321373, 1.0734645]),
+            ];
+
+            assert_eq!(expected_population, population);
+        }
+
+        #[cfg(feature = "parallel")]
+        #[test]
+        fn parallel_breeding_matches_sequential() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population = population(&mut rng);
+            let mut ranked = crate::selection::by_fitness(&population);
+            ranked.reverse();
+
+            let ga = GeneticAlgorithm::new(
+                TournamentSelection::new(2).unwrap(),
+                UniformCrossover,
+                NormalMutation::new(0.5, 0.5).unwrap(),
+            );
+
+            let sequential: Vec<TestIndividual> = (0..population.len())
+                .map(|idx| ga.breed_child(1234, idx, &population, &ranked, 0))
+                .collect::<Result<_, _>>()
+                .unwrap();
+
+            let parallel = ga
+                .breed_children(1234, &population, &ranked, population.len(), 0)
+                .unwrap();
+
+            assert_eq!(sequential, parallel);
+        }
+
+        /// Selects 1000 individuals out of four that all have zero fitness
+        /// and counts how many times each of them came up.
+        fn zero_fitness_histogram(method: &impl SelectionMethod) -> BTreeMap<usize, usize> {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population = vec![TestIndividual::new(0.0); 4];
+
+            method
+                .select_many(&mut rng, &population, 1000)
+                .unwrap()
+                .into_iter()
+                .fold(Default::default(), |mut histogram, individual| {
+                    let idx = population
+                        .iter()
+                        .position(|other| std::ptr::eq(other, individual))
+                        .unwrap();
+
+                    *histogram.entry(idx).or_default() += 1;
+
+                    histogram
+                })
+        }
+
+        #[test]
+        fn all_zero_fitness() {
+            // Nobody's better than anybody else, so everybody gets picked
+            // roughly the same number of times
+            let expected_histogram = maplit::btreemap! {
+                0 => 243,
+                1 => 265,
+                2 => 261,
+                3 => 231,
+            };
+
+            assert_eq!(
+                expected_histogram,
+                zero_fitness_histogram(&RouletteWheelSelection::new())
+            );
+
+            assert_eq!(
+                expected_histogram,
+                zero_fitness_histogram(&StochasticUniversalSampling::new())
+            );
+        }
+
+        #[test]
+        fn empty_population() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let ga = GeneticAlgorithm::new(
+                RouletteWheelSelection::new(),
+                UniformCrossover,
+                NormalMutation::new(0.5, 0.5).unwrap(),
+            );
+
+            assert_eq!(
+                ga.evolve::<TestIndividual>(&mut rng, &[]).unwrap_err(),
+                crate::Error::EmptyPopulation,
+            );
+        }
     }
 }
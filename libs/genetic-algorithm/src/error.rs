@@ -0,0 +1,72 @@
+use crate::Replacement;
+use rand::distributions::WeightedError;
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum Error {
+    #[error("mutation chance must be within 0.0..=1.0, got {0}")]
+    InvalidMutationChance(f32),
+
+    #[error("mutation sigma must be non-negative, got {0}")]
+    InvalidMutationSigma(f32),
+
+    #[error("half-life must be positive, got {0}")]
+    InvalidHalfLife(f32),
+
+    #[error("gene range must not be empty, got {min}..={max}")]
+    InvalidGeneRange { min: f32, max: f32 },
+
+    #[error("parents must have the same number of genes, got {a} and {b}")]
+    ParentLengthMismatch { a: usize, b: usize },
+
+    #[error("tournament size must be at least 1, got {0}")]
+    InvalidTournamentSize(usize),
+
+    #[error("truncation ratio must be within (0.0, 1.0], got {0}")]
+    InvalidTruncationRatio(f32),
+
+    #[error("invalid replacement strategy: {0:?}")]
+    InvalidReplacement(Replacement),
+
+    #[error("replacement strategy needs at least {required} individuals, got {actual}")]
+    PopulationTooSmall { required: usize, actual: usize },
+
+    #[error("blend crossover alpha must be non-negative, got {0}")]
+    InvalidBlendAlpha(f32),
+
+    #[error("distribution index must be non-negative, got {0}")]
+    InvalidDistributionIndex(f32),
+
+    #[error("crossover needs at least one cut point, got {0}")]
+    InvalidCrossoverPoints(usize),
+
+    #[error("network needs at least two layers, got {0}")]
+    NotEnoughLayers(usize),
+
+    #[error("expected chromosome of {expected} genes, got {actual}")]
+    ChromosomeLengthMismatch { expected: usize, actual: usize },
+
+    #[error("island model needs at least one island")]
+    NoIslands,
+
+    #[error("migration interval must be at least 1, got {0}")]
+    InvalidMigrationInterval(usize),
+
+    #[error("expected populations of {expected} islands, got {actual}")]
+    IslandCountMismatch { expected: usize, actual: usize },
+
+    #[error("compatibility threshold must be positive, got {0}")]
+    InvalidCompatibilityThreshold(f32),
+
+    #[error("expected {expected} objectives, got {actual}")]
+    ObjectiveCountMismatch { expected: usize, actual: usize },
+
+    #[error("novelty needs at least one neighbour, got {0}")]
+    InvalidNeighbours(usize),
+
+    #[error("population must not be empty")]
+    EmptyPopulation,
+
+    #[error("cannot select an individual: {0}")]
+    InvalidFitness(#[from] WeightedError),
+}
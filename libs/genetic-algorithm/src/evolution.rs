@@ -0,0 +1,295 @@
+use std::time::{Duration, Instant};
+
+use rand::RngCore;
+
+use crate::{Error, GeneticAlgorithm, Individual, SelectionMethod, Statistics};
+
+/// Runs [`GeneticAlgorithm::evolve()`] over and over, until one of the
+/// termination criteria is met.
+///
+/// Evolution always stops after `max_generations`; the other criteria are
+/// optional:
+///
+/// ```text
+/// let outcome = Evolution::new(1000)
+///     .with_target_fitness(42.0)
+///     .with_stagnation(50)
+///     .run(&ga, &mut rng, population, |population| evaluate(population))?;
+/// ```
+#[derive(Clone, Debug)]
+pub struct Evolution {
+    max_generations: usize,
+    target_fitness: Option<f32>,
+    time_limit: Option<Duration>,
+    stagnation: Option<usize>,
+}
+
+/// Why the evolution stopped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Termination {
+    MaxGenerations,
+    TargetFitness,
+    TimeLimit,
+    Stagnation,
+}
+
+#[derive(Debug)]
+pub struct Outcome<I> {
+    /// The best individual ever seen - not necessarily a member of the
+    /// final population.
+    pub best: I,
+
+    /// The last evaluated population.
+    pub population: Vec<I>,
+
+    /// Statistics of each evaluated generation, the initial population
+    /// included.
+    pub history: Vec<Statistics>,
+
+    pub termination: Termination,
+}
+
+impl Evolution {
+    pub fn new(max_generations: usize) -> Self {
+        Self {
+            max_generations,
+            target_fitness: None,
+            time_limit: None,
+            stagnation: None,
+        }
+    }
+
+    /// Stops as soon as any individual reaches given fitness.
+    pub fn with_target_fitness(self, target_fitness: f32) -> Self {
+        Self {
+            target_fitness: Some(target_fitness),
+            ..self
+        }
+    }
+
+    /// Stops once a generation finishes after the time limit has passed.
+    ///
+    /// Note that this is checked between generations only, so a single
+    /// slow evaluation can overshoot it.
+    pub fn with_time_limit(self, time_limit: Duration) -> Self {
+        Self {
+            time_limit: Some(time_limit),
+            ..self
+        }
+    }
+
+    /// Stops when the best fitness hasn't improved for given number of
+    /// generations.
+    pub fn with_stagnation(self, generations: usize) -> Self {
+        Self {
+            stagnation: Some(generations),
+            ..self
+        }
+    }
+
+    /// Evolves given population until termination.
+    ///
+    /// `evaluate` receives freshly created individuals (starting with the
+    /// initial population) and has to return them with their fitness
+    /// known - e.g. after running them through a simulation; evaluated
+    /// individuals then go through [`GeneticAlgorithm::truncate()`].
+    pub fn run<S, I>(
+        &self,
+        ga: &GeneticAlgorithm<S>,
+        rng: &mut dyn RngCore,
+        population: Vec<I>,
+        mut evaluate: impl FnMut(Vec<I>) -> Vec<I>,
+    ) -> Result<Outcome<I>, Error>
+    where
+        S: SelectionMethod + Sync,
+        I: Individual + Clone + Send + Sync,
+    {
+        let started_at = self.time_limit.map(|_| Instant::now());
+        let mut population = ga.truncate(evaluate(population));
+        let mut history = Vec::new();
+        let mut best: Option<I> = None;
+        let mut stagnant = 0;
+
+        for generation in 0.. {
+            let fittest = population
+                .iter()
+                .reduce(|a, b| if b.fitness() > a.fitness() { b } else { a })
+                .ok_or(Error::EmptyPopulation)?;
+
+            match &best {
+                Some(best) if fittest.fitness() <= best.fitness() => {
+                    stagnant += 1;
+                }
+                _ => {
+                    best = Some(fittest.clone());
+                    stagnant = 0;
+                }
+            }
+
+            history.push(Statistics::new(generation, &population)?);
+
+            if let Some(termination) =
+                self.termination(generation, best.as_ref(), stagnant, started_at)
+            {
+                return Ok(Outcome {
+                    best: best.expect("best is set in the first generation"),
+                    population,
+                    history,
+                    termination,
+                });
+            }
+
+            population = ga.truncate(evaluate(ga.evolve_at(rng, &population, generation)?));
+        }
+
+        unreachable!()
+    }
+
+    fn termination<I>(
+        &self,
+        generation: usize,
+        best: Option<&I>,
+        stagnant: usize,
+        started_at: Option<Instant>,
+    ) -> Option<Termination>
+    where
+        I: Individual,
+    {
+        let best = best.map_or(f32::MIN, |best| best.fitness());
+
+        if self.target_fitness.is_some_and(|target| best >= target) {
+            return Some(Termination::TargetFitness);
+        }
+
+        if generation >= self.max_generations {
+            return Some(Termination::MaxGenerations);
+        }
+
+        if self.stagnation.is_some_and(|limit| stagnant >= limit) {
+            return Some(Termination::Stagnation);
+        }
+
+        if let (Some(limit), Some(started_at)) = (self.time_limit, started_at) {
+            if started_at.elapsed() >= limit {
+                return Some(Termination::TimeLimit);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::chromosome::tests::histogram::{individual, population, TestIndividual};
+    use crate::{NormalMutation, Replacement, TournamentSelection, UniformCrossover};
+
+    fn ga() -> GeneticAlgorithm<TournamentSelection> {
+        GeneticAlgorithm::new(
+            TournamentSelection::new(2).unwrap(),
+            UniformCrossover,
+            NormalMutation::new(0.5, 0.5).unwrap(),
+        )
+        .with_replacement(Replacement::Elitism { elites: 1 })
+    }
+
+    #[test]
+    fn stops_after_max_generations() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population(&mut rng);
+
+        let outcome = Evolution::new(10)
+            .run(&ga(), &mut rng, population, |p| p)
+            .unwrap();
+
+        assert_eq!(outcome.termination, Termination::MaxGenerations);
+
+        // Initial population + 10 evolved ones
+        assert_eq!(outcome.history.len(), 11);
+        assert_eq!(outcome.history[10].generation, 10);
+
+        approx::assert_relative_eq!(outcome.best.fitness(), outcome.history[10].max_fitness);
+    }
+
+    #[test]
+    fn stops_at_target_fitness() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population(&mut rng);
+
+        let outcome = Evolution::new(1000)
+            .with_target_fitness(10.0)
+            .run(&ga(), &mut rng, population, |p| p)
+            .unwrap();
+
+        assert_eq!(outcome.termination, Termination::TargetFitness);
+        assert!(outcome.best.fitness() >= 10.0);
+        assert!(outcome.history.len() < 1000);
+    }
+
+    #[test]
+    fn stops_when_stagnating() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = vec![individual(&[1.0, 1.0, 1.0]); 4];
+
+        // Identical parents and no mutation - nothing can ever improve
+        let ga = GeneticAlgorithm::new(
+            TournamentSelection::new(2).unwrap(),
+            UniformCrossover,
+            NormalMutation::new(0.0, 0.5).unwrap(),
+        );
+
+        let outcome = Evolution::new(1000)
+            .with_stagnation(5)
+            .run(&ga, &mut rng, population, |p| p)
+            .unwrap();
+
+        assert_eq!(outcome.termination, Termination::Stagnation);
+        assert_eq!(outcome.history.len(), 6);
+    }
+
+    #[test]
+    fn stops_after_time_limit() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population(&mut rng);
+
+        let outcome = Evolution::new(1000)
+            .with_time_limit(std::time::Duration::ZERO)
+            .run(&ga(), &mut rng, population, |p| p)
+            .unwrap();
+
+        assert_eq!(outcome.termination, Termination::TimeLimit);
+        assert_eq!(outcome.history.len(), 1);
+    }
+
+    #[test]
+    fn evaluates_every_generation() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population(&mut rng);
+        let mut evaluations = 0;
+
+        Evolution::new(3)
+            .run(&ga(), &mut rng, population, |population| {
+                evaluations += 1;
+                population
+            })
+            .unwrap();
+
+        assert_eq!(evaluations, 4);
+    }
+
+    #[test]
+    fn rejects_empty_population() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        assert_eq!(
+            Evolution::new(10)
+                .run::<_, TestIndividual>(&ga(), &mut rng, vec![], |p| p)
+                .unwrap_err(),
+            crate::Error::EmptyPopulation,
+        );
+    }
+}
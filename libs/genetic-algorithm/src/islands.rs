@@ -0,0 +1,370 @@
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+use crate::selection::{by_fitness, indices_by_fitness};
+use crate::{Error, GeneticAlgorithm, Individual, SelectionMethod};
+
+/// Which islands send migrants where.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Topology {
+    /// Each island receives migrants from the previous one, the first
+    /// island from the last one.
+    #[default]
+    Ring,
+
+    /// Each island receives the best migrants of all the other islands
+    /// combined.
+    FullyConnected,
+
+    /// Each island receives migrants from another, randomly chosen, island.
+    Random,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Migration {
+    pub topology: Topology,
+
+    /// Migration happens after every `interval` generations.
+    pub interval: usize,
+
+    /// How many individuals each island receives; they replace its worst
+    /// individuals.
+    pub migrants: usize,
+}
+
+/// Several sub-populations (islands) evolving independently, exchanging
+/// their best individuals every now and then.
+///
+/// Isolated islands tend to explore different parts of the search space,
+/// which keeps the overall population from converging prematurely, while
+/// migration still lets good solutions spread.
+pub struct IslandModel<S> {
+    islands: Vec<GeneticAlgorithm<S>>,
+    migration: Migration,
+}
+
+impl<S> IslandModel<S>
+where
+    S: SelectionMethod + Sync,
+{
+    /// Creates a model with one island per given genetic algorithm - so
+    /// each island can evolve with different settings.
+    pub fn new(islands: Vec<GeneticAlgorithm<S>>, migration: Migration) -> Result<Self, Error> {
+        if islands.is_empty() {
+            return Err(Error::NoIslands);
+        }
+
+        if migration.interval == 0 {
+            return Err(Error::InvalidMigrationInterval(migration.interval));
+        }
+
+        Ok(Self { islands, migration })
+    }
+
+    pub fn islands(&self) -> &[GeneticAlgorithm<S>] {
+        &self.islands
+    }
+
+    pub fn migration(&self) -> &Migration {
+        &self.migration
+    }
+
+    /// Evolves each (already evaluated) island's population with its own
+    /// genetic algorithm - migrating first, if it's the time for it.
+    pub fn evolve_at<I>(
+        &self,
+        rng: &mut dyn RngCore,
+        populations: &[Vec<I>],
+        generation: usize,
+    ) -> Result<Vec<Vec<I>>, Error>
+    where
+        I: Individual + Clone + Send + Sync,
+    {
+        if populations.len() != self.islands.len() {
+            return Err(Error::IslandCountMismatch {
+                expected: self.islands.len(),
+                actual: populations.len(),
+            });
+        }
+
+        let mut populations = populations.to_vec();
+
+        if (generation + 1) % self.migration.interval == 0 {
+            self.migrate(rng, &mut populations)?;
+        }
+
+        populations
+            .iter()
+            .zip(&self.islands)
+            .map(|(population, ga)| ga.evolve_at(rng, population, generation))
+            .collect()
+    }
+
+    /// Replaces the worst individuals of each island with the best ones of
+    /// other islands, according to the topology.
+    ///
+    /// Migrants keep their fitness, so they can be selected as parents
+    /// right away.
+    pub fn migrate<I>(&self, rng: &mut dyn RngCore, populations: &mut [Vec<I>]) -> Result<(), Error>
+    where
+        I: Individual + Clone,
+    {
+        let count = populations.len();
+        let migrants = self.migration.migrants;
+
+        if count < 2 || migrants == 0 {
+            return Ok(());
+        }
+
+        if let Some(population) = populations.iter().find(|p| p.len() < migrants) {
+            return Err(Error::PopulationTooSmall {
+                required: migrants,
+                actual: population.len(),
+            });
+        }
+
+        // Emigrants are picked before anybody arrives anywhere, so that
+        // nobody hops over multiple islands in a single migration
+        let emigrants: Vec<Vec<I>> = populations
+            .iter()
+            .map(|population| {
+                by_fitness(population)
+                    .into_iter()
+                    .rev()
+                    .take(migrants)
+                    .cloned()
+                    .collect()
+            })
+            .collect();
+
+        for (idx, population) in populations.iter_mut().enumerate() {
+            let immigrants = match self.migration.topology {
+                Topology::Ring => emigrants[(idx + count - 1) % count].clone(),
+
+                Topology::FullyConnected => {
+                    let pool: Vec<_> = emigrants
+                        .iter()
+                        .enumerate()
+                        .filter(|(source, _)| *source != idx)
+                        .flat_map(|(_, emigrants)| emigrants.iter().cloned())
+                        .collect();
+
+                    by_fitness(&pool)
+                        .into_iter()
+                        .rev()
+                        .take(migrants)
+                        .cloned()
+                        .collect()
+                }
+
+                Topology::Random => {
+                    let source = (idx + rng.gen_range(1..count)) % count;
+                    emigrants[source].clone()
+                }
+            };
+
+            for (slot, immigrant) in indices_by_fitness(population).into_iter().zip(immigrants) {
+                population[slot] = immigrant;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::chromosome::tests::histogram::{individual, TestIndividual};
+    use crate::{NormalMutation, RouletteWheelSelection, TournamentSelection, UniformCrossover};
+
+    fn islands(topology: Topology, count: usize) -> IslandModel<RouletteWheelSelection> {
+        let islands = (0..count)
+            .map(|_| {
+                GeneticAlgorithm::new(
+                    RouletteWheelSelection::new(),
+                    UniformCrossover,
+                    NormalMutation::new(0.5, 0.5).unwrap(),
+                )
+            })
+            .collect();
+
+        IslandModel::new(
+            islands,
+            Migration {
+                topology,
+                interval: 2,
+                migrants: 1,
+            },
+        )
+        .unwrap()
+    }
+
+    /// Island `n` contains individuals of fitness `10n + 1`, `10n + 2`
+    /// and `10n + 3`.
+    fn archipelago(count: usize) -> Vec<Vec<TestIndividual>> {
+        (0..count)
+            .map(|n| {
+                (1..=3)
+                    .map(|i| TestIndividual::new((10 * n + i) as f32))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn fitnesses(populations: &[Vec<TestIndividual>]) -> Vec<Vec<i32>> {
+        populations
+            .iter()
+            .map(|population| population.iter().map(|i| i.fitness() as i32).collect())
+            .collect()
+    }
+
+    #[test]
+    fn ring_migration() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut populations = archipelago(3);
+
+        islands(Topology::Ring, 3)
+            .migrate(&mut rng, &mut populations)
+            .unwrap();
+
+        // Best of the previous island replaces the worst one
+        assert_eq!(
+            fitnesses(&populations),
+            [vec![23, 2, 3], vec![3, 12, 13], vec![13, 22, 23]],
+        );
+    }
+
+    #[test]
+    fn fully_connected_migration() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut populations = archipelago(3);
+
+        islands(Topology::FullyConnected, 3)
+            .migrate(&mut rng, &mut populations)
+            .unwrap();
+
+        // Best of all the other islands replaces the worst one
+        assert_eq!(
+            fitnesses(&populations),
+            [vec![23, 2, 3], vec![23, 12, 13], vec![13, 22, 23]],
+        );
+    }
+
+    #[test]
+    fn random_migration() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut populations = archipelago(3);
+
+        islands(Topology::Random, 3)
+            .migrate(&mut rng, &mut populations)
+            .unwrap();
+
+        assert_eq!(
+            fitnesses(&populations),
+            [vec![23, 2, 3], vec![23, 12, 13], vec![3, 22, 23]],
+        );
+    }
+
+    #[test]
+    fn single_island_does_not_migrate() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut populations = archipelago(1);
+
+        islands(Topology::Ring, 1)
+            .migrate(&mut rng, &mut populations)
+            .unwrap();
+
+        assert_eq!(fitnesses(&populations), [vec![1, 2, 3]]);
+    }
+
+    #[test]
+    fn islands_migrate_on_interval() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = || {
+            GeneticAlgorithm::new(
+                TournamentSelection::new(2).unwrap(),
+                UniformCrossover,
+                NormalMutation::new(0.0, 0.0).unwrap(),
+            )
+        };
+
+        let model = IslandModel::new(
+            vec![ga(), ga()],
+            Migration {
+                topology: Topology::Ring,
+                interval: 2,
+                migrants: 1,
+            },
+        )
+        .unwrap();
+
+        // Island 0 is full of zeros, island 1 full of ones - and since
+        // nothing mutates, every non-zero gene on island 0 had to come
+        // from a migrant
+        let mut populations = vec![
+            vec![individual(&[0.0, 0.0]); 4],
+            vec![individual(&[1.0, 1.0]); 4],
+        ];
+
+        let has_migrants = |populations: &[Vec<TestIndividual>]| {
+            populations[0]
+                .iter()
+                .any(|individual| individual.chromosome().iter().any(|&gene| gene > 0.0))
+        };
+
+        // Interval is 2, so the first generation doesn't migrate...
+        populations = model.evolve_at(&mut rng, &populations, 0).unwrap();
+        assert!(!has_migrants(&populations));
+
+        // ... but the second one does
+        populations = model.evolve_at(&mut rng, &populations, 1).unwrap();
+        assert!(has_migrants(&populations));
+    }
+
+    #[test]
+    fn invalid_island_model() {
+        let migration = Migration {
+            topology: Topology::Ring,
+            interval: 1,
+            migrants: 1,
+        };
+
+        assert!(matches!(
+            IslandModel::<RouletteWheelSelection>::new(vec![], migration.clone()),
+            Err(crate::Error::NoIslands),
+        ));
+
+        assert!(matches!(
+            IslandModel::new(
+                vec![GeneticAlgorithm::new(
+                    TournamentSelection::new(2).unwrap(),
+                    UniformCrossover,
+                    NormalMutation::new(0.5, 0.5).unwrap(),
+                )],
+                Migration {
+                    interval: 0,
+                    ..migration
+                },
+            ),
+            Err(crate::Error::InvalidMigrationInterval(0)),
+        ));
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        assert_eq!(
+            islands(Topology::Ring, 3)
+                .evolve_at(&mut rng, &archipelago(2), 0)
+                .unwrap_err(),
+            crate::Error::IslandCountMismatch {
+                expected: 3,
+                actual: 2
+            },
+        );
+    }
+}
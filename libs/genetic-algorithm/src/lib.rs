@@ -1,21 +1,51 @@
 mod chromosome;
+mod error;
+mod evolution;
+mod islands;
 mod mutation;
+mod novelty;
+mod nsga;
+mod replacement;
 mod selection;
+mod speciation;
+mod statistics;
 
-use rand::RngCore;
+use rand::{seq::SliceRandom, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 pub use chromosome::{Chromosome, Individual};
-pub use mutation::{GaussianMutation, MutationMethod};
-pub use selection::{CrossoverMethod, RouletteWheelSelection, SelectionMethod, UniformCrossover};
+pub use error::Error;
+pub use evolution::{Evolution, Outcome, Termination};
+pub use islands::{IslandModel, Migration, Topology};
+#[allow(deprecated)]
+pub use mutation::GaussianMutation;
+pub use mutation::{
+    DecayingMutation, MutationMethod, NormalMutation, PolynomialMutation, SelfAdaptiveMutation,
+    StepSizes, UniformMutation,
+};
+pub use novelty::NoveltyArchive;
+pub use nsga::{
+    crowding_distance, dominates, non_dominated_sort, pareto_front, MultiObjectiveIndividual, Nsga2,
+};
+pub use replacement::Replacement;
+pub use selection::{
+    BlendCrossover, CrossoverMethod, KPointCrossover, NeuronCrossover, RankSelection,
+    RouletteWheelSelection, SelectionMethod, SimulatedBinaryCrossover, SinglePointCrossover,
+    StochasticUniversalSampling, TournamentSelection, TruncationSelection, UniformCrossover,
+};
+pub use speciation::{diversity, Speciation};
+pub use statistics::Statistics;
 
 pub struct GeneticAlgorithm<S> {
     selection_method: S,
     crossover_method: Box<dyn CrossoverMethod>,
     mutation_method: Box<dyn MutationMethod>,
+    replacement: Replacement,
+    fitness_sharing: Option<Speciation>,
 }
 
 impl<S> GeneticAlgorithm<S>
 where
-    S: SelectionMethod,
+    S: SelectionMethod + Sync,
 {
     pub fn new(
         selection_method: S,
@@ -26,27 +56,182 @@ where
             selection_method,
             crossover_method: Box::new(crossover_method),
             mutation_method: Box::new(mutation_method),
+            replacement: Replacement::default(),
+            fitness_sharing: None,
         }
     }
 
-    pub fn evolve<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> Vec<I>
+    pub fn with_replacement(self, replacement: Replacement) -> Self {
+        Self {
+            replacement,
+            ..self
+        }
+    }
+
+    /// Divides each individual's fitness by the size of its species before
+    /// selecting parents, so that a single lineage cannot take over the
+    /// whole population.
+    ///
+    /// Only selection sees the shared fitness - replacement (e.g. picking
+    /// elites) still goes by the original one.
+    pub fn with_fitness_sharing(self, speciation: Speciation) -> Self {
+        Self {
+            fitness_sharing: Some(speciation),
+            ..self
+        }
+    }
+
+    pub fn evolve<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> Result<Vec<I>, Error>
     where
-        I: Individual,
+        I: Individual + Send + Sync,
+    {
+        self.evolve_at(rng, population, 0)
+    }
+
+    /// Like [`Self::evolve()`], but tells mutation which generation the
+    /// children belong to - see [`MutationMethod::mutate_at()`].
+    pub fn evolve_at<I>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+        generation: usize,
+    ) -> Result<Vec<I>, Error>
+    where
+        I: Individual + Send + Sync,
     {
-        assert!(!population.is_empty());
+        if population.is_empty() {
+            return Err(Error::EmptyPopulation);
+        }
+
+        let (survivors, children) = self.replacement.plan(population.len())?;
 
-        (0..population.len())
-            .map(|_| {
-                let parent_a = self.selection_method.select(rng, population).chromosome();
+        // Best individuals go first
+        let mut ranked = selection::by_fitness(population);
+        ranked.reverse();
 
-                let parent_b = self.selection_method.select(rng, population).chromosome();
+        // Instead of sharing `rng`, each child gets its own stream derived
+        // from a single number drawn from it - this way children don't
+        // depend on the order in which they are bred, so breeding them in
+        // parallel gives exactly the same results as doing it one by one
+        let seed = rng.next_u64();
 
-                let mut child = self.crossover_method.crossover(rng, parent_a, parent_b);
+        let children = match &self.fitness_sharing {
+            Some(speciation) => {
+                let shared = speciation.share(population);
+                self.breed_children(seed, &shared, &ranked, children, generation)?
+            }
+
+            None => self.breed_children(seed, population, &ranked, children, generation)?,
+        };
+
+        Ok(ranked[..survivors]
+            .iter()
+            .map(|survivor| I::create(survivor.chromosome().clone()))
+            .chain(children)
+            .collect())
+    }
 
-                self.mutation_method.mutate(rng, &mut child);
+    /// Cuts evaluated candidates returned by [`Self::evolve()`] down to the
+    /// next generation - see [`Replacement::truncate()`].
+    pub fn truncate<I>(&self, evaluated: Vec<I>) -> Vec<I>
+    where
+        I: Individual,
+    {
+        self.replacement.truncate(evaluated)
+    }
 
-                I::create(child)
-            })
+    #[cfg(not(feature = "parallel"))]
+    pub(crate) fn breed_children<P, I>(
+        &self,
+        seed: u64,
+        population: &[P],
+        ranked: &[&I],
+        count: usize,
+        generation: usize,
+    ) -> Result<Vec<I>, Error>
+    where
+        P: Individual + Sync,
+        I: Individual + Send + Sync,
+    {
+        (0..count)
+            .map(|idx| self.breed_child(seed, idx, population, ranked, generation))
             .collect()
     }
+
+    #[cfg(feature = "parallel")]
+    pub(crate) fn breed_children<P, I>(
+        &self,
+        seed: u64,
+        population: &[P],
+        ranked: &[&I],
+        count: usize,
+        generation: usize,
+    ) -> Result<Vec<I>, Error>
+    where
+        P: Individual + Sync,
+        I: Individual + Send + Sync,
+    {
+        use rayon::prelude::*;
+
+        (0..count)
+            .into_par_iter()
+            .map(|idx| self.breed_child(seed, idx, population, ranked, generation))
+            .collect()
+    }
+
+    /// Breeds a single child, with parents selected from `population`
+    /// (which - thanks to fitness sharing - doesn't have to be the same
+    /// type as the children).
+    fn breed_child<P, I>(
+        &self,
+        seed: u64,
+        idx: usize,
+        population: &[P],
+        ranked: &[&I],
+        generation: usize,
+    ) -> Result<I, Error>
+    where
+        P: Individual,
+        I: Individual,
+    {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        rng.set_stream(idx as u64);
+
+        let rng = &mut rng;
+
+        let (parent_a, parent_b) = match self.replacement.parents() {
+            Some(mu) => {
+                let parents = &ranked[..mu];
+
+                (
+                    parents.choose(rng).unwrap().chromosome(),
+                    parents.choose(rng).unwrap().chromosome(),
+                )
+            }
+
+            None => (
+                self.selection_method.select(rng, population)?.chromosome(),
+                self.selection_method.select(rng, population)?.chromosome(),
+            ),
+        };
+
+        self.breed(rng, parent_a, parent_b, generation)
+    }
+
+    fn breed<I>(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+        generation: usize,
+    ) -> Result<I, Error>
+    where
+        I: Individual,
+    {
+        let mut child = self.crossover_method.crossover(rng, parent_a, parent_b)?;
+
+        self.mutation_method.mutate_at(rng, &mut child, generation);
+
+        Ok(I::create(child))
+    }
 }
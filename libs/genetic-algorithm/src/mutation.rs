@@ -1,9 +1,14 @@
-use rand::{RngCore, Rng};
+use rand::{Rng, RngCore};
+use rand_distr::StandardNormal;
 
-use crate::chromosome::Chromosome;
+use crate::{chromosome::Chromosome, Error};
 
+/// Nudges genes by a uniformly distributed amount.
+///
+/// This is what the simulation has historically used (back when it was
+/// called [`GaussianMutation`]); for the real thing see [`NormalMutation`].
 #[derive(Clone, Debug)]
-pub struct GaussianMutation {
+pub struct UniformMutation {
     /// Probability of changing a gene:
     /// - 0.0 = no genes will be touched
     /// - 1.0 = all genes will be touched
@@ -15,15 +20,17 @@ pub struct GaussianMutation {
     coeff: f32,
 }
 
-impl GaussianMutation {
-    pub fn new(chance: f32, coeff: f32) -> Self {
-        assert!(chance >= 0.0 && chance <= 1.0);
+impl UniformMutation {
+    pub fn new(chance: f32, coeff: f32) -> Result<Self, Error> {
+        if !(0.0..=1.0).contains(&chance) {
+            return Err(Error::InvalidMutationChance(chance));
+        }
 
-        Self { chance, coeff }
+        Ok(Self { chance, coeff })
     }
 }
 
-impl MutationMethod for GaussianMutation {
+impl MutationMethod for UniformMutation {
     fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
         for gene in child.iter_mut() {
             let sign = if rng.gen_bool(0.5) { -1.0 } else { 1.0 };
@@ -35,8 +42,298 @@ impl MutationMethod for GaussianMutation {
     }
 }
 
-pub trait MutationMethod {
+/// Old name of [`UniformMutation`] - despite it, changes were never
+/// normally distributed.
+#[deprecated(note = "use `UniformMutation` (same behavior) or `NormalMutation`")]
+pub type GaussianMutation = UniformMutation;
+
+/// Nudges genes by a normally distributed amount.
+#[derive(Clone, Debug)]
+pub struct NormalMutation {
+    /// Probability of changing a gene:
+    /// - 0.0 = no genes will be touched
+    /// - 1.0 = all genes will be touched
+    chance: f32,
+
+    /// Standard deviation of that change - about 68% of the touched genes
+    /// will be += or -= by at most that much.
+    sigma: f32,
+}
+
+impl NormalMutation {
+    pub fn new(chance: f32, sigma: f32) -> Result<Self, Error> {
+        if !(0.0..=1.0).contains(&chance) {
+            return Err(Error::InvalidMutationChance(chance));
+        }
+
+        if sigma.is_nan() || sigma < 0.0 {
+            return Err(Error::InvalidMutationSigma(sigma));
+        }
+
+        Ok(Self { chance, sigma })
+    }
+}
+
+impl MutationMethod for NormalMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        gaussian(rng, child, self.chance, self.sigma);
+    }
+}
+
+/// Self-adaptive mutation, as in evolution strategies: chromosome carries
+/// its own step sizes (at its end), which get mutated - and so evolve -
+/// together with the rest of the genes.
+///
+/// ```text
+/// StepSizes::Single:  [ x1, x2, ..., xn, sigma ]
+/// StepSizes::PerGene: [ x1, x2, ..., xn, sigma1, sigma2, ..., sigman ]
+/// ```
+///
+/// Use [`Self::extend()`] to append initial step sizes to a chromosome and
+/// [`Self::genes()`] to strip them off.
+#[derive(Clone, Debug)]
+pub struct SelfAdaptiveMutation {
+    steps: StepSizes,
+
+    /// Step sizes never go below this, otherwise they tend to collapse to
+    /// zero and the evolution stops moving.
+    min_sigma: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StepSizes {
+    /// One step size shared by all the genes
+    Single,
+
+    /// Separate step size for each gene
+    PerGene,
+}
+
+impl SelfAdaptiveMutation {
+    pub fn new(steps: StepSizes, min_sigma: f32) -> Result<Self, Error> {
+        if min_sigma.is_nan() || min_sigma < 0.0 {
+            return Err(Error::InvalidMutationSigma(min_sigma));
+        }
+
+        Ok(Self { steps, min_sigma })
+    }
+
+    /// Appends initial step sizes to given genes.
+    pub fn extend(&self, genes: impl IntoIterator<Item = f32>, sigma: f32) -> Chromosome {
+        let genes: Vec<_> = genes.into_iter().collect();
+
+        let steps = match self.steps {
+            StepSizes::Single => 1,
+            StepSizes::PerGene => genes.len(),
+        };
+
+        genes
+            .into_iter()
+            .chain(std::iter::repeat_n(sigma, steps))
+            .collect()
+    }
+
+    /// Returns the genes, without step sizes.
+    pub fn genes<'a>(&self, chromosome: &'a Chromosome) -> impl Iterator<Item = f32> + 'a {
+        chromosome.iter().copied().take(self.split(chromosome))
+    }
+
+    /// Number of genes that aren't step sizes.
+    fn split(&self, chromosome: &Chromosome) -> usize {
+        match self.steps {
+            StepSizes::Single => chromosome.len().saturating_sub(1),
+            StepSizes::PerGene => chromosome.len() / 2,
+        }
+    }
+}
+
+impl MutationMethod for SelfAdaptiveMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        let n = self.split(child);
+
+        if n == 0 {
+            return;
+        }
+
+        let mut normal = || rng.sample::<f32, _>(StandardNormal);
+
+        // Learning rates recommended by Schwefel; `global` is shared by all
+        // step sizes of the chromosome, `local` is drawn for each of them
+        let single = 1.0 / (n as f32).sqrt();
+        let global = 1.0 / (2.0 * n as f32).sqrt();
+        let local = 1.0 / (2.0 * (n as f32).sqrt()).sqrt();
+
+        let (genes, sigmas) = child.genes_mut().split_at_mut(n);
+
+        match self.steps {
+            StepSizes::Single => {
+                let sigma = &mut sigmas[0];
+                *sigma = (*sigma * (single * normal()).exp()).max(self.min_sigma);
+
+                for gene in genes {
+                    *gene += *sigma * normal();
+                }
+            }
+
+            StepSizes::PerGene => {
+                let shared = global * normal();
+
+                for (gene, sigma) in genes.iter_mut().zip(sigmas) {
+                    *sigma = (*sigma * (shared + local * normal()).exp()).max(self.min_sigma);
+                    *gene += *sigma * normal();
+                }
+            }
+        }
+    }
+}
+
+/// Polynomial mutation (Deb & Goyal) - perturbs genes according to a
+/// polynomial distribution and keeps them within `min..=max`.
+///
+/// `eta` (the distribution index) controls the spread:
+/// - 0.0 = changes can easily reach across the whole range
+/// - 20.0 = changes are mostly tiny
+#[derive(Clone, Debug)]
+pub struct PolynomialMutation {
+    chance: f32,
+    eta: f32,
+    min: f32,
+    max: f32,
+}
+
+impl PolynomialMutation {
+    pub fn new(chance: f32, eta: f32, min: f32, max: f32) -> Result<Self, Error> {
+        if !(0.0..=1.0).contains(&chance) {
+            return Err(Error::InvalidMutationChance(chance));
+        }
+
+        if eta.is_nan() || eta < 0.0 {
+            return Err(Error::InvalidDistributionIndex(eta));
+        }
+
+        if min.is_nan() || max.is_nan() || min >= max {
+            return Err(Error::InvalidGeneRange { min, max });
+        }
+
+        Ok(Self {
+            chance,
+            eta,
+            min,
+            max,
+        })
+    }
+}
+
+impl MutationMethod for PolynomialMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        let exponent = 1.0 / (self.eta + 1.0);
+
+        for gene in child.iter_mut() {
+            if !rng.gen_bool(self.chance as _) {
+                continue;
+            }
+
+            let u: f32 = rng.gen();
+
+            let delta = if u < 0.5 {
+                (2.0 * u).powf(exponent) - 1.0
+            } else {
+                1.0 - (2.0 * (1.0 - u)).powf(exponent)
+            };
+
+            *gene = (*gene + delta * (self.max - self.min)).clamp(self.min, self.max);
+        }
+    }
+}
+
+/// [`NormalMutation`] whose chance decays exponentially with generations,
+/// from `initial_chance` towards `final_chance`:
+///
+/// ```text
+/// chance = final + (initial - final) * 0.5 ^ (generation / half_life)
+/// ```
+///
+/// Early generations explore, later ones fine-tune. Generation is passed
+/// through [`MutationMethod::mutate_at()`] - plain [`MutationMethod::mutate()`]
+/// mutates as if it was the very first generation.
+#[derive(Clone, Debug)]
+pub struct DecayingMutation {
+    initial_chance: f32,
+    final_chance: f32,
+
+    /// Number of generations after which the chance gets halfway from
+    /// initial to final
+    half_life: f32,
+
+    sigma: f32,
+}
+
+impl DecayingMutation {
+    pub fn new(
+        initial_chance: f32,
+        final_chance: f32,
+        half_life: f32,
+        sigma: f32,
+    ) -> Result<Self, Error> {
+        for chance in [initial_chance, final_chance] {
+            if !(0.0..=1.0).contains(&chance) {
+                return Err(Error::InvalidMutationChance(chance));
+            }
+        }
+
+        if half_life.is_nan() || half_life <= 0.0 {
+            return Err(Error::InvalidHalfLife(half_life));
+        }
+
+        if sigma.is_nan() || sigma < 0.0 {
+            return Err(Error::InvalidMutationSigma(sigma));
+        }
+
+        Ok(Self {
+            initial_chance,
+            final_chance,
+            half_life,
+            sigma,
+        })
+    }
+
+    pub fn chance(&self, generation: usize) -> f32 {
+        let decay = 0.5_f32.powf(generation as f32 / self.half_life);
+
+        self.final_chance + (self.initial_chance - self.final_chance) * decay
+    }
+}
+
+impl MutationMethod for DecayingMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        self.mutate_at(rng, child, 0);
+    }
+
+    fn mutate_at(&self, rng: &mut dyn RngCore, child: &mut Chromosome, generation: usize) {
+        gaussian(rng, child, self.chance(generation), self.sigma);
+    }
+}
+
+pub trait MutationMethod: Send + Sync {
     fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome);
+
+    /// Mutates a child born in given generation.
+    ///
+    /// Only methods whose behaviour changes over time (e.g.
+    /// [`DecayingMutation`]) care about the generation; by default this is
+    /// just [`Self::mutate()`].
+    fn mutate_at(&self, rng: &mut dyn RngCore, child: &mut Chromosome, generation: usize) {
+        let _ = generation;
+        self.mutate(rng, child);
+    }
+}
+
+fn gaussian(rng: &mut dyn RngCore, child: &mut Chromosome, chance: f32, sigma: f32) {
+    for gene in child.iter_mut() {
+        if rng.gen_bool(chance as _) {
+            *gene += sigma * rng.sample::<f32, _>(StandardNormal);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -51,7 +348,9 @@ mod tests {
 
         let mut rng = ChaCha8Rng::from_seed(Default::default());
 
-        GaussianMutation::new(chance, coeff).mutate(&mut rng, &mut child);
+        UniformMutation::new(chance, coeff)
+            .unwrap()
+            .mutate(&mut rng, &mut child);
 
         child.into_iter().collect()
     }
@@ -144,4 +443,231 @@ mod tests {
             }
         }
     }
+
+    fn chromosome() -> Chromosome {
+        vec![1.0, 2.0, 3.0, 4.0, 5.0].into_iter().collect()
+    }
+
+    fn mutated(method: &impl MutationMethod) -> Vec<f32> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut child = chromosome();
+
+        method.mutate(&mut rng, &mut child);
+
+        child.into_iter().collect()
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn gaussian_is_the_old_name_of_uniform() {
+        assert_eq!(
+            mutated(&GaussianMutation::new(0.5, 0.5).unwrap()),
+            mutated(&UniformMutation::new(0.5, 0.5).unwrap()),
+        );
+    }
+
+    mod normal {
+        use super::*;
+
+        #[test]
+        fn given_zero_chance_does_not_change_the_original_chromosome() {
+            approx::assert_relative_eq!(
+                mutated(&NormalMutation::new(0.0, 0.5).unwrap()).as_slice(),
+                [1.0, 2.0, 3.0, 4.0, 5.0].as_ref(),
+            );
+        }
+
+        #[test]
+        fn given_max_chance_entirely_changes_the_original_chromosome() {
+            approx::assert_relative_eq!(
+                mutated(&NormalMutation::new(1.0, 0.5).unwrap()).as_slice(),
+                [1.6888486, 2.2026734, 2.4018655, 3.0324764, 4.664113].as_ref(),
+            );
+        }
+
+        #[test]
+        fn is_normally_distributed() {
+            let method = NormalMutation::new(1.0, 2.0).unwrap();
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child: Chromosome = vec![0.0; 10_000].into_iter().collect();
+
+            method.mutate(&mut rng, &mut child);
+
+            let deltas: Vec<_> = child.into_iter().collect();
+            let mean = deltas.iter().sum::<f32>() / deltas.len() as f32;
+            let within_sigma = deltas.iter().filter(|delta| delta.abs() <= 2.0).count();
+            let within_two_sigmas = deltas.iter().filter(|delta| delta.abs() <= 4.0).count();
+
+            // ---
+            // | roughly 68% and 95%, as the three-sigma rule says; the old,
+            // | uniform mutation would get 100% for both
+            // ---
+            assert!(mean.abs() < 0.05, "mean = {mean}");
+            assert_eq!(within_sigma, 6806);
+            assert_eq!(within_two_sigmas, 9537);
+        }
+
+        #[test]
+        fn rejects_invalid_sigma() {
+            assert_eq!(
+                NormalMutation::new(0.5, -1.0).unwrap_err(),
+                Error::InvalidMutationSigma(-1.0),
+            );
+        }
+    }
+
+    mod self_adaptive {
+        use super::*;
+
+        #[test]
+        fn extend_and_genes() {
+            let single = SelfAdaptiveMutation::new(StepSizes::Single, 0.0).unwrap();
+            let per_gene = SelfAdaptiveMutation::new(StepSizes::PerGene, 0.0).unwrap();
+
+            let a = single.extend(vec![1.0, 2.0, 3.0], 0.5);
+            let b = per_gene.extend(vec![1.0, 2.0, 3.0], 0.5);
+
+            assert_eq!(a.iter().copied().collect::<Vec<_>>(), [1.0, 2.0, 3.0, 0.5]);
+            assert_eq!(
+                b.iter().copied().collect::<Vec<_>>(),
+                [1.0, 2.0, 3.0, 0.5, 0.5, 0.5],
+            );
+
+            assert_eq!(single.genes(&a).collect::<Vec<_>>(), [1.0, 2.0, 3.0]);
+            assert_eq!(per_gene.genes(&b).collect::<Vec<_>>(), [1.0, 2.0, 3.0]);
+        }
+
+        #[test]
+        fn single_step_size() {
+            let method = SelfAdaptiveMutation::new(StepSizes::Single, 0.0).unwrap();
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child = method.extend(vec![1.0, 2.0, 3.0, 4.0], 0.5);
+
+            method.mutate(&mut rng, &mut child);
+
+            approx::assert_relative_eq!(
+                child.into_iter().collect::<Vec<_>>().as_slice(),
+                [1.4036081, 0.8088623, 1.073253, 3.3311076, 0.9957107].as_ref(),
+            );
+        }
+
+        #[test]
+        fn step_size_per_gene() {
+            let method = SelfAdaptiveMutation::new(StepSizes::PerGene, 0.0).unwrap();
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child = method.extend(vec![1.0, 2.0], 0.5);
+
+            method.mutate(&mut rng, &mut child);
+
+            approx::assert_relative_eq!(
+                child.into_iter().collect::<Vec<_>>().as_slice(),
+                [-0.51577914, 1.7883282, 1.267089, 0.315094].as_ref(),
+            );
+        }
+
+        #[test]
+        fn step_sizes_do_not_collapse() {
+            let method = SelfAdaptiveMutation::new(StepSizes::PerGene, 0.1).unwrap();
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child = method.extend(vec![0.0; 4], 0.0);
+
+            for _ in 0..100 {
+                method.mutate(&mut rng, &mut child);
+            }
+
+            assert!(child.iter().skip(4).all(|&sigma| sigma >= 0.1));
+        }
+    }
+
+    mod polynomial {
+        use super::*;
+
+        #[test]
+        fn changes_the_original_chromosome() {
+            approx::assert_relative_eq!(
+                mutated(&PolynomialMutation::new(1.0, 20.0, -10.0, 10.0).unwrap()).as_slice(),
+                [0.08609748, 3.0390508, 4.559149, 4.2883425, 5.6944075].as_ref(),
+            );
+        }
+
+        #[test]
+        fn stays_within_range() {
+            let method = PolynomialMutation::new(1.0, 0.0, 0.0, 5.0).unwrap();
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child = chromosome();
+
+            for _ in 0..100 {
+                method.mutate(&mut rng, &mut child);
+                assert!(child.iter().all(|gene| (0.0..=5.0).contains(gene)));
+            }
+        }
+
+        #[test]
+        fn rejects_invalid_parameters() {
+            assert_eq!(
+                PolynomialMutation::new(0.5, -1.0, 0.0, 1.0).unwrap_err(),
+                Error::InvalidDistributionIndex(-1.0),
+            );
+            assert_eq!(
+                PolynomialMutation::new(0.5, 20.0, 1.0, 1.0).unwrap_err(),
+                Error::InvalidGeneRange { min: 1.0, max: 1.0 },
+            );
+        }
+    }
+
+    mod decaying {
+        use super::*;
+
+        #[test]
+        fn chance() {
+            let method = DecayingMutation::new(0.5, 0.1, 10.0, 0.5).unwrap();
+
+            approx::assert_relative_eq!(method.chance(0), 0.5);
+            approx::assert_relative_eq!(method.chance(10), 0.3);
+            approx::assert_relative_eq!(method.chance(20), 0.2);
+            approx::assert_relative_eq!(method.chance(1000), 0.1);
+        }
+
+        #[test]
+        fn first_generation_is_plain_gaussian() {
+            let decaying = mutated(&DecayingMutation::new(0.5, 0.0, 10.0, 0.5).unwrap());
+            let gaussian = mutated(&NormalMutation::new(0.5, 0.5).unwrap());
+
+            approx::assert_relative_eq!(decaying.as_slice(), gaussian.as_slice());
+        }
+
+        #[test]
+        fn eventually_stops_mutating() {
+            let method = DecayingMutation::new(1.0, 0.0, 1.0, 0.5).unwrap();
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child = chromosome();
+
+            method.mutate_at(&mut rng, &mut child, 1000);
+
+            approx::assert_relative_eq!(
+                child.into_iter().collect::<Vec<_>>().as_slice(),
+                [1.0, 2.0, 3.0, 4.0, 5.0].as_ref(),
+            );
+        }
+
+        #[test]
+        fn rejects_invalid_half_life() {
+            assert_eq!(
+                DecayingMutation::new(0.5, 0.1, 0.0, 0.5).unwrap_err(),
+                Error::InvalidHalfLife(0.0),
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_chance() {
+        assert_eq!(
+            UniformMutation::new(1.5, 0.1).unwrap_err(),
+            Error::InvalidMutationChance(1.5),
+        );
+        assert_eq!(
+            UniformMutation::new(-0.1, 0.1).unwrap_err(),
+            Error::InvalidMutationChance(-0.1),
+        );
+    }
 }
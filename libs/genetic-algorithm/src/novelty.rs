@@ -0,0 +1,176 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{chromosome::distance, Error};
+
+/// Novelty search - rewards individuals for behaving differently than
+/// anybody before them, instead of for being good at the task.
+///
+/// Each individual is described by its behaviour (a bunch of numbers,
+/// e.g. where it ended up); its novelty is then the mean distance to its
+/// `neighbours` nearest behaviours - both of the current population and
+/// of the archive, which remembers the most novel behaviours seen so far.
+///
+/// This helps when fitness alone gives no clue where to go, e.g. when
+/// nobody manages to score anything early on.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NoveltyArchive {
+    neighbours: usize,
+    capacity: usize,
+    per_generation: usize,
+    behaviours: VecDeque<Vec<f32>>,
+}
+
+impl NoveltyArchive {
+    /// Creates an empty archive that adds `per_generation` most novel
+    /// behaviours after each evaluation, forgetting the oldest ones once
+    /// it holds `capacity` of them.
+    pub fn new(neighbours: usize, capacity: usize, per_generation: usize) -> Result<Self, Error> {
+        if neighbours == 0 {
+            return Err(Error::InvalidNeighbours(neighbours));
+        }
+
+        Ok(Self {
+            neighbours,
+            capacity,
+            per_generation,
+            behaviours: VecDeque::new(),
+        })
+    }
+
+    pub fn neighbours(&self) -> usize {
+        self.neighbours
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn behaviours(&self) -> impl Iterator<Item = &[f32]> {
+        self.behaviours.iter().map(|behaviour| behaviour.as_slice())
+    }
+
+    pub fn len(&self) -> usize {
+        self.behaviours.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.behaviours.is_empty()
+    }
+
+    /// Returns novelty of each behaviour, compared against the rest of
+    /// `behaviours` and the archive.
+    pub fn score(&self, behaviours: &[Vec<f32>]) -> Vec<f32> {
+        behaviours
+            .iter()
+            .enumerate()
+            .map(|(idx, behaviour)| {
+                let population = behaviours
+                    .iter()
+                    .enumerate()
+                    .filter(|(other, _)| *other != idx)
+                    .map(|(_, other)| other);
+
+                let mut distances: Vec<_> = population
+                    .chain(&self.behaviours)
+                    .map(|other| distance(behaviour, other))
+                    .collect();
+
+                distances.sort_by(|a, b| a.total_cmp(b));
+                distances.truncate(self.neighbours);
+
+                if distances.is_empty() {
+                    0.0
+                } else {
+                    distances.iter().sum::<f32>() / distances.len() as f32
+                }
+            })
+            .collect()
+    }
+
+    /// Scores behaviours (see [`Self::score()`]) and archives the most
+    /// novel ones.
+    pub fn evaluate(&mut self, behaviours: &[Vec<f32>]) -> Vec<f32> {
+        let scores = self.score(behaviours);
+
+        let mut order: Vec<_> = (0..behaviours.len()).collect();
+        order.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]));
+
+        for idx in order.into_iter().take(self.per_generation) {
+            self.behaviours.push_back(behaviours[idx].clone());
+        }
+
+        while self.behaviours.len() > self.capacity {
+            self.behaviours.pop_front();
+        }
+
+        scores
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn behaviours(values: &[f32]) -> Vec<Vec<f32>> {
+        values.iter().map(|&value| vec![value]).collect()
+    }
+
+    #[test]
+    fn score() {
+        let archive = NoveltyArchive::new(2, 10, 1).unwrap();
+        let scores = archive.score(&behaviours(&[0.0, 1.0, 2.0, 10.0]));
+
+        approx::assert_relative_eq!(scores.as_slice(), [1.5, 1.0, 1.5, 8.5].as_slice());
+    }
+
+    #[test]
+    fn score_includes_archive() {
+        let mut archive = NoveltyArchive::new(1, 10, 1).unwrap();
+        archive.evaluate(&behaviours(&[0.0, 5.0]));
+
+        // Both behaviours are equally novel, so the first one got archived
+        assert_eq!(archive.behaviours().collect::<Vec<_>>(), [[0.0]]);
+
+        let scores = archive.score(&behaviours(&[1.0, 4.0]));
+
+        approx::assert_relative_eq!(scores.as_slice(), [1.0, 3.0].as_slice());
+    }
+
+    #[test]
+    fn evaluate_archives_most_novel() {
+        let mut archive = NoveltyArchive::new(1, 10, 2).unwrap();
+        archive.evaluate(&behaviours(&[0.0, 1.0, 5.0, 9.0]));
+
+        assert_eq!(archive.behaviours().collect::<Vec<_>>(), [[5.0], [9.0]]);
+    }
+
+    #[test]
+    fn forgets_oldest() {
+        let mut archive = NoveltyArchive::new(1, 2, 1).unwrap();
+
+        for value in [1.0, 2.0, 3.0] {
+            archive.evaluate(&behaviours(&[value]));
+        }
+
+        assert_eq!(archive.len(), 2);
+        assert_eq!(archive.behaviours().collect::<Vec<_>>(), [[2.0], [3.0]]);
+    }
+
+    #[test]
+    fn lonely_behaviour() {
+        let archive = NoveltyArchive::new(3, 10, 1).unwrap();
+
+        assert_eq!(archive.score(&behaviours(&[1.0])), [0.0]);
+    }
+
+    #[test]
+    fn invalid_neighbours() {
+        assert_eq!(
+            NoveltyArchive::new(0, 10, 1).err(),
+            Some(Error::InvalidNeighbours(0))
+        );
+    }
+}
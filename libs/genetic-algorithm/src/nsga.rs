@@ -0,0 +1,482 @@
+use std::f32::consts::PI;
+
+use rand::RngCore;
+
+use crate::{
+    Chromosome, CrossoverMethod, Error, GeneticAlgorithm, Individual, MutationMethod,
+    TournamentSelection,
+};
+
+/// Individual that is judged by more than one number, e.g. by how much it
+/// ate *and* how far it had to travel for it.
+///
+/// All objectives are maximised - if something should be minimised (like
+/// the distance), return it negated.
+pub trait MultiObjectiveIndividual: Individual {
+    fn objectives(&self) -> Vec<f32>;
+}
+
+/// NSGA-II - evolves a population towards the Pareto front of its
+/// objectives, instead of towards a single best fitness.
+///
+/// Each generation:
+///
+/// - the population is sorted into fronts (see [`non_dominated_sort()`]),
+///   with ties inside of a front broken by [`crowding_distance()`],
+///
+/// - the better half (`⌈N / 2⌉` individuals) survives,
+///
+/// - the other half (`⌊N / 2⌋` individuals) is replaced with children of
+///   the survivors, whose parents are picked by binary tournaments
+///   comparing first fronts and then crowding distances.
+///
+/// Survivors get evaluated again alongside their children, so they compete
+/// with them the next generation - which makes this elitist the same way
+/// the paper is, but on a smaller scale: the paper merges `N` parents with
+/// `N` children and keeps the best `N` of those `2N`, while here the
+/// population of `N` is split into `N / 2` parents and `N / 2` children.
+/// This keeps the population's size (e.g. the number of birds in a world)
+/// constant, at the cost of a smaller elite.
+pub struct Nsga2 {
+    ga: GeneticAlgorithm<TournamentSelection>,
+}
+
+impl Nsga2 {
+    pub fn new(
+        crossover_method: impl CrossoverMethod + 'static,
+        mutation_method: impl MutationMethod + 'static,
+    ) -> Self {
+        let selection_method =
+            TournamentSelection::new(2).expect("tournament of two is always valid");
+
+        Self {
+            ga: GeneticAlgorithm::new(selection_method, crossover_method, mutation_method),
+        }
+    }
+
+    pub fn evolve<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> Result<Vec<I>, Error>
+    where
+        I: MultiObjectiveIndividual + Send + Sync,
+    {
+        self.evolve_at(rng, population, 0)
+    }
+
+    /// Like [`Self::evolve()`], but tells mutation which generation the
+    /// children belong to - see [`MutationMethod::mutate_at()`].
+    pub fn evolve_at<I>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+        generation: usize,
+    ) -> Result<Vec<I>, Error>
+    where
+        I: MultiObjectiveIndividual + Send + Sync,
+    {
+        if population.len() < 2 {
+            return Err(Error::PopulationTooSmall {
+                required: 2,
+                actual: population.len(),
+            });
+        }
+
+        let mut crowded = crowd(population)?;
+
+        // Best individuals go first
+        crowded.sort_by(|a, b| b.fitness().total_cmp(&a.fitness()));
+
+        let children = population.len() / 2;
+        let survivors = &crowded[..population.len() - children];
+
+        // Survivors are the only ones allowed to breed
+        let children =
+            self.ga
+                .breed_children::<_, I>(rng.next_u64(), survivors, &[], children, generation)?;
+
+        Ok(survivors
+            .iter()
+            .map(|survivor| I::create(survivor.chromosome().clone()))
+            .chain(children)
+            .collect())
+    }
+}
+
+/// Returns individuals no other individual dominates.
+pub fn pareto_front<I>(population: &[I]) -> Result<Vec<&I>, Error>
+where
+    I: MultiObjectiveIndividual,
+{
+    let objectives = objectives(population)?;
+
+    Ok(non_dominated_sort(&objectives)
+        .into_iter()
+        .next()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|idx| &population[idx])
+        .collect())
+}
+
+/// Whether `a` is at least as good as `b` in every objective and strictly
+/// better in at least one of them.
+pub fn dominates(a: &[f32], b: &[f32]) -> bool {
+    let mut better = false;
+
+    for (a, b) in a.iter().zip(b) {
+        if a < b {
+            return false;
+        }
+
+        better |= a > b;
+    }
+
+    better
+}
+
+/// Splits individuals (given by their objectives) into fronts, returning
+/// their indices:
+///
+/// - the first front contains individuals nobody dominates,
+/// - the second one contains individuals dominated only by the first one,
+/// - and so on.
+pub fn non_dominated_sort(objectives: &[Vec<f32>]) -> Vec<Vec<usize>> {
+    let len = objectives.len();
+
+    // For each individual: whom it dominates and by how many it's dominated
+    let mut dominated: Vec<Vec<usize>> = vec![Vec::new(); len];
+    let mut domination_counts = vec![0; len];
+
+    for a in 0..len {
+        for b in (a + 1)..len {
+            if dominates(&objectives[a], &objectives[b]) {
+                dominated[a].push(b);
+                domination_counts[b] += 1;
+            } else if dominates(&objectives[b], &objectives[a]) {
+                dominated[b].push(a);
+                domination_counts[a] += 1;
+            }
+        }
+    }
+
+    let mut fronts = Vec::new();
+
+    let mut front: Vec<_> = (0..len)
+        .filter(|&idx| domination_counts[idx] == 0)
+        .collect();
+
+    while !front.is_empty() {
+        let mut next = Vec::new();
+
+        for &a in &front {
+            for &b in &dominated[a] {
+                domination_counts[b] -= 1;
+
+                if domination_counts[b] == 0 {
+                    next.push(b);
+                }
+            }
+        }
+
+        next.sort_unstable();
+        fronts.push(front);
+        front = next;
+    }
+
+    fronts
+}
+
+/// Returns how far each member of `front` is from its neighbours - the
+/// larger the distance, the less crowded the part of the front it lies
+/// in.
+///
+/// Individuals on the edges (best or worst at some objective) get an
+/// infinite distance, so that they are always preferred.
+pub fn crowding_distance(objectives: &[Vec<f32>], front: &[usize]) -> Vec<f32> {
+    let mut distances = vec![0.0; front.len()];
+
+    if front.len() <= 2 {
+        distances.fill(f32::INFINITY);
+        return distances;
+    }
+
+    let count = front.iter().map(|&idx| objectives[idx].len()).min();
+
+    // Values of given objective, in the same order as `front`
+    let values_of = |objective: usize| -> Vec<f32> {
+        front
+            .iter()
+            .map(|&idx| objectives[idx][objective])
+            .collect()
+    };
+
+    for objective in 0..count.unwrap_or_default() {
+        let values = values_of(objective);
+
+        let mut order: Vec<_> = (0..front.len()).collect();
+        order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+
+        let first = order[0];
+        let last = order[order.len() - 1];
+        let range = values[last] - values[first];
+
+        // Objective everybody scores the same on says nothing about who's
+        // crowded - in particular, it has no edges worth keeping
+        if range <= 0.0 {
+            continue;
+        }
+
+        distances[first] = f32::INFINITY;
+        distances[last] = f32::INFINITY;
+
+        for window in order.windows(3) {
+            distances[window[1]] += (values[window[2]] - values[window[0]]) / range;
+        }
+    }
+
+    distances
+}
+
+fn objectives<I>(population: &[I]) -> Result<Vec<Vec<f32>>, Error>
+where
+    I: MultiObjectiveIndividual,
+{
+    let objectives: Vec<_> = population.iter().map(|i| i.objectives()).collect();
+    let expected = objectives.first().map_or(0, |o| o.len());
+
+    if let Some(o) = objectives.iter().find(|o| o.len() != expected) {
+        return Err(Error::ObjectiveCountMismatch {
+            expected,
+            actual: o.len(),
+        });
+    }
+
+    Ok(objectives)
+}
+
+fn crowd<I>(population: &[I]) -> Result<Vec<Crowded<'_, I>>, Error>
+where
+    I: MultiObjectiveIndividual,
+{
+    let objectives = objectives(population)?;
+    let mut crowded = Vec::with_capacity(population.len());
+
+    for (rank, front) in non_dominated_sort(&objectives).into_iter().enumerate() {
+        let distances = crowding_distance(&objectives, &front);
+
+        for (idx, distance) in front.into_iter().zip(distances) {
+            crowded.push((
+                idx,
+                Crowded {
+                    individual: &population[idx],
+                    // ---
+                    // | Squeezes the crowding distance into 0.0..=0.5, so
+                    // | that it can break ties within a front, but never
+                    // | make up for a worse front
+                    // ---------------------------- v------------v
+                    fitness: -(rank as f32) + distance.atan() / PI,
+                },
+            ));
+        }
+    }
+
+    // Keep the original order, so that ties get resolved the same way
+    // regardless of how fronts got built
+    crowded.sort_by_key(|(idx, _)| *idx);
+
+    Ok(crowded.into_iter().map(|(_, crowded)| crowded).collect())
+}
+
+/// Individual as seen by the selection method - its fitness encodes both
+/// the front it belongs to and its crowding distance.
+pub(crate) struct Crowded<'a, I> {
+    individual: &'a I,
+    fitness: f32,
+}
+
+impl<I> Individual for Crowded<'_, I>
+where
+    I: Individual,
+{
+    fn create(_: Chromosome) -> Self {
+        unreachable!("crowded individuals are only ever selected, never created")
+    }
+
+    fn fitness(&self) -> f32 {
+        self.fitness
+    }
+
+    fn chromosome(&self) -> &Chromosome {
+        self.individual.chromosome()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::chromosome::tests::histogram::{individual, population, TestIndividual};
+    use crate::{NormalMutation, UniformCrossover};
+
+    fn objectives(values: &[[f32; 2]]) -> Vec<Vec<f32>> {
+        values.iter().map(|v| v.to_vec()).collect()
+    }
+
+    #[test]
+    fn test_dominates() {
+        assert!(dominates(&[2.0, 2.0], &[1.0, 2.0]));
+        assert!(dominates(&[2.0, 3.0], &[1.0, 2.0]));
+
+        assert!(!dominates(&[1.0, 2.0], &[2.0, 2.0]));
+        assert!(!dominates(&[2.0, 1.0], &[1.0, 2.0]));
+        assert!(!dominates(&[2.0, 2.0], &[2.0, 2.0]));
+    }
+
+    #[test]
+    fn test_non_dominated_sort() {
+        let objectives = objectives(&[
+            [1.0, 1.0],
+            [3.0, 1.0],
+            [2.0, 2.0],
+            [1.0, 3.0],
+            [0.0, 0.0],
+            [2.0, 1.0],
+        ]);
+
+        assert_eq!(
+            non_dominated_sort(&objectives),
+            [vec![1, 2, 3], vec![5], vec![0], vec![4]],
+        );
+
+        assert!(non_dominated_sort(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_crowding_distance() {
+        let objectives = objectives(&[[0.0, 4.0], [1.0, 3.0], [3.0, 1.0], [4.0, 0.0]]);
+        let distances = crowding_distance(&objectives, &[0, 1, 2, 3]);
+
+        assert_eq!(distances[0], f32::INFINITY);
+        approx::assert_relative_eq!(distances[1], 1.5);
+        approx::assert_relative_eq!(distances[2], 1.5);
+        assert_eq!(distances[3], f32::INFINITY);
+
+        // Too small fronts consist of edges only
+        assert_eq!(
+            crowding_distance(&objectives, &[1, 2]),
+            [f32::INFINITY, f32::INFINITY],
+        );
+    }
+
+    #[test]
+    fn crowding_distance_ignores_constant_objectives() {
+        let objectives = objectives(&[[0.0, 1.0], [1.0, 1.0], [3.0, 1.0], [4.0, 1.0]]);
+        let distances = crowding_distance(&objectives, &[1, 0, 3, 2]);
+
+        // Only the first objective has edges
+        assert_eq!(distances[0], 0.75);
+        assert_eq!(distances[1], f32::INFINITY);
+        assert_eq!(distances[2], f32::INFINITY);
+        assert_eq!(distances[3], 0.75);
+    }
+
+    fn nsga2() -> Nsga2 {
+        Nsga2::new(UniformCrossover, NormalMutation::new(0.5, 0.2).unwrap())
+    }
+
+    #[test]
+    fn test_pareto_front() {
+        let population = vec![
+            individual(&[1.0, 1.0]),
+            individual(&[3.0, 1.0]),
+            individual(&[2.0, 2.0]),
+            individual(&[2.0, 1.0]),
+            individual(&[1.0, 3.0]),
+        ];
+
+        assert_eq!(
+            pareto_front(&population).unwrap(),
+            [&population[1], &population[2], &population[4]],
+        );
+    }
+
+    #[test]
+    fn nsga2_keeps_pareto_front() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let population = vec![
+            individual(&[0.0, 0.0]),
+            individual(&[3.0, 1.0]),
+            individual(&[1.0, 1.0]),
+            individual(&[2.0, 2.0]),
+            individual(&[0.5, 0.5]),
+            individual(&[1.0, 3.0]),
+        ];
+
+        let evolved = nsga2().evolve(&mut rng, &population).unwrap();
+
+        assert_eq!(evolved.len(), population.len());
+
+        // Survivors go first - edges of the front are the least
+        // crowded, so they come before the middle
+        assert_eq!(
+            &evolved[..3],
+            [
+                individual(&[3.0, 1.0]),
+                individual(&[1.0, 3.0]),
+                individual(&[2.0, 2.0]),
+            ],
+        );
+    }
+
+    #[test]
+    fn nsga2_improves_all_objectives() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let nsga = nsga2();
+
+        let mean = |population: &[TestIndividual], objective: usize| {
+            population
+                .iter()
+                .map(|individual| individual.chromosome()[objective])
+                .sum::<f32>()
+                / population.len() as f32
+        };
+
+        let mut population = population(&mut rng);
+        let initial = (mean(&population, 0), mean(&population, 1));
+
+        for generation in 0..20 {
+            population = nsga.evolve_at(&mut rng, &population, generation).unwrap();
+        }
+
+        assert!(mean(&population, 0) > initial.0);
+        assert!(mean(&population, 1) > initial.1);
+    }
+
+    #[test]
+    fn nsga2_objective_count_mismatch() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = vec![individual(&[1.0, 2.0]), individual(&[1.0])];
+
+        assert_eq!(
+            nsga2().evolve(&mut rng, &population).err(),
+            Some(crate::Error::ObjectiveCountMismatch {
+                expected: 2,
+                actual: 1
+            }),
+        );
+    }
+
+    #[test]
+    fn nsga2_population_too_small() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        assert_eq!(
+            nsga2().evolve(&mut rng, &[individual(&[1.0])]).err(),
+            Some(crate::Error::PopulationTooSmall {
+                required: 2,
+                actual: 1
+            }),
+        );
+    }
+}
@@ -0,0 +1,355 @@
+use std::cmp::Ordering;
+
+use crate::{Error, Individual};
+
+/// Decides who makes it into the next generation.
+///
+/// `population` below means the (already evaluated) population passed to
+/// [`crate::GeneticAlgorithm::evolve()`]; survivors are carried over as-is,
+/// without crossover or mutation.
+///
+/// Children's fitness is not known until they get evaluated, so strategies
+/// that pick the next generation among parents *and* children - (μ+λ) and
+/// (μ,λ) - work in two steps: `evolve()` returns candidates, which, once
+/// evaluated, go through [`Replacement::truncate()`]; [`crate::Evolution`]
+/// does that on its own.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Replacement {
+    /// Children replace the entire population.
+    #[default]
+    Generational,
+
+    /// The best `elites` individuals survive, the rest of the population is
+    /// replaced by children.
+    Elitism { elites: usize },
+
+    /// The best `mu` individuals become parents of `lambda` children and
+    /// then compete with them - next generation is the best `mu` of those
+    /// parents and children together.
+    ///
+    /// `evolve()` returns the `mu` parents followed by the `lambda`
+    /// children; parents are picked uniformly from the best `mu`, the
+    /// selection method is not used.
+    MuPlusLambda { mu: usize, lambda: usize },
+
+    /// Like [`Self::MuPlusLambda`], but parents don't survive - next
+    /// generation is the best `mu` of the `lambda` children.
+    ///
+    /// `evolve()` returns just the `lambda` children.
+    MuCommaLambda { mu: usize, lambda: usize },
+
+    /// Only the worst `replaced` individuals are replaced by children.
+    SteadyState { replaced: usize },
+}
+
+impl Replacement {
+    /// Returns how many of the best individuals survive and how many children
+    /// have to be bred for a population of given size.
+    pub(crate) fn plan(&self, population: usize) -> Result<(usize, usize), Error> {
+        let ensure_population = |required: usize| {
+            if required > population {
+                Err(Error::PopulationTooSmall {
+                    required,
+                    actual: population,
+                })
+            } else {
+                Ok(())
+            }
+        };
+
+        match *self {
+            Self::Generational => Ok((0, population)),
+
+            Self::Elitism { elites } => {
+                ensure_population(elites)?;
+                Ok((elites, population - elites))
+            }
+
+            Self::MuPlusLambda { mu, lambda } => {
+                if mu == 0 || lambda == 0 {
+                    return Err(Error::InvalidReplacement(*self));
+                }
+
+                ensure_population(mu)?;
+                Ok((mu, lambda))
+            }
+
+            Self::MuCommaLambda { mu, lambda } => {
+                if mu == 0 || lambda < mu {
+                    return Err(Error::InvalidReplacement(*self));
+                }
+
+                ensure_population(mu)?;
+                Ok((0, lambda))
+            }
+
+            Self::SteadyState { replaced } => {
+                if replaced == 0 {
+                    return Err(Error::InvalidReplacement(*self));
+                }
+
+                ensure_population(replaced)?;
+                Ok((population - replaced, replaced))
+            }
+        }
+    }
+
+    /// Cuts evaluated candidates returned by `evolve()` down to the next
+    /// generation: for (μ+λ) and (μ,λ) that's the best `mu` of them (best
+    /// first), other strategies leave the population as it is.
+    pub fn truncate<I>(&self, mut evaluated: Vec<I>) -> Vec<I>
+    where
+        I: Individual,
+    {
+        let mu = match *self {
+            Self::MuPlusLambda { mu, .. } | Self::MuCommaLambda { mu, .. } => mu,
+            _ => return evaluated,
+        };
+
+        // Stable, so on ties parents (which go first) win over children
+        evaluated.sort_by(|a, b| {
+            b.fitness()
+                .partial_cmp(&a.fitness())
+                .unwrap_or(Ordering::Equal)
+        });
+
+        evaluated.truncate(mu);
+        evaluated
+    }
+
+    /// Whether parents are picked uniformly from the best `mu` individuals
+    /// instead of by the selection method.
+    pub(crate) fn parents(&self) -> Option<usize> {
+        match *self {
+            Self::MuPlusLambda { mu, .. } | Self::MuCommaLambda { mu, .. } => Some(mu),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::chromosome::tests::histogram::{individual, population, TestIndividual};
+    use crate::{
+        Evolution, GeneticAlgorithm, NormalMutation, RouletteWheelSelection, TournamentSelection,
+        UniformCrossover,
+    };
+    use test_case::test_case;
+
+    fn best_fitness(population: &[TestIndividual]) -> f32 {
+        population
+            .iter()
+            .map(|individual| individual.fitness())
+            .fold(f32::MIN, f32::max)
+    }
+
+    #[test_case(Replacement::Elitism { elites: 1 } ; "elitism")]
+    #[test_case(Replacement::Elitism { elites: 3 } ; "elitism with more elites")]
+    #[test_case(Replacement::MuPlusLambda { mu: 2, lambda: 6 } ; "mu plus lambda")]
+    #[test_case(Replacement::SteadyState { replaced: 2 } ; "steady state")]
+    fn best_fitness_never_decreases(replacement: Replacement) {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        // Very aggressive mutation, so that without elitism the best
+        // individual would get lost pretty much immediately
+        let ga = GeneticAlgorithm::new(
+            TournamentSelection::new(2).unwrap(),
+            UniformCrossover,
+            NormalMutation::new(1.0, 2.0).unwrap(),
+        )
+        .with_replacement(replacement);
+
+        let mut population = population(&mut rng);
+        let mut best = best_fitness(&population);
+
+        for _ in 0..50 {
+            population = ga.truncate(ga.evolve(&mut rng, &population).unwrap());
+
+            let new_best = best_fitness(&population);
+            assert!(new_best >= best, "{new_best} < {best}");
+            best = new_best;
+        }
+    }
+
+    #[test]
+    fn generational_can_lose_the_best() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            TournamentSelection::new(2).unwrap(),
+            UniformCrossover,
+            NormalMutation::new(1.0, 2.0).unwrap(),
+        );
+
+        let mut population = population(&mut rng);
+        let mut decreased = false;
+
+        for _ in 0..50 {
+            let next = ga.evolve(&mut rng, &population).unwrap();
+            decreased |= best_fitness(&next) < best_fitness(&population);
+            population = next;
+        }
+
+        assert!(decreased);
+    }
+
+    #[test]
+    fn steady_state_keeps_the_best_unchanged() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover,
+            NormalMutation::new(1.0, 2.0).unwrap(),
+        )
+        .with_replacement(Replacement::SteadyState { replaced: 1 });
+
+        let population = vec![
+            individual(&[1.0, 1.0, 1.0]), // fitness = 3.0
+            individual(&[0.0, 0.0, 0.0]), // fitness = 0.0
+            individual(&[1.0, 2.0, 4.0]), // fitness = 7.0
+            individual(&[1.0, 2.0, 1.0]), // fitness = 4.0
+        ];
+
+        let evolved = ga.evolve(&mut rng, &population).unwrap();
+
+        // Survivors go first, best to worst; the worst one got replaced
+        assert_eq!(evolved.len(), 4);
+        assert_eq!(
+            evolved[..3],
+            [
+                population[2].clone(),
+                population[3].clone(),
+                population[0].clone()
+            ]
+        );
+        assert!(!evolved[3..].contains(&population[1]));
+    }
+
+    #[test]
+    fn truncate() {
+        let candidates = || {
+            vec![
+                TestIndividual::new(3.0),
+                TestIndividual::new(7.0),
+                TestIndividual::new(0.0),
+                TestIndividual::new(4.0),
+            ]
+        };
+
+        let fitnesses = |population: Vec<TestIndividual>| -> Vec<f32> {
+            population.iter().map(|i| i.fitness()).collect()
+        };
+
+        assert_eq!(
+            fitnesses(Replacement::MuPlusLambda { mu: 2, lambda: 2 }.truncate(candidates())),
+            [7.0, 4.0]
+        );
+
+        // Other strategies don't need truncating
+        assert_eq!(
+            fitnesses(Replacement::Elitism { elites: 2 }.truncate(candidates())),
+            [3.0, 7.0, 0.0, 4.0]
+        );
+    }
+
+    #[test_case(Replacement::MuPlusLambda { mu: 3, lambda: 6 }, 9 ; "mu plus lambda")]
+    #[test_case(Replacement::MuCommaLambda { mu: 3, lambda: 6 }, 6 ; "mu comma lambda")]
+    fn mu_lambda_keeps_population_at_mu(replacement: Replacement, candidates: usize) {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover,
+            NormalMutation::new(0.5, 0.5).unwrap(),
+        )
+        .with_replacement(replacement);
+
+        let population = population(&mut rng);
+        let mut evaluated = Vec::new();
+
+        let outcome = Evolution::new(20)
+            .run(&ga, &mut rng, population, |population| {
+                evaluated.push(population.len());
+                population
+            })
+            .unwrap();
+
+        // Initial population gets truncated, too
+        assert_eq!(evaluated[0], 8);
+        assert!(evaluated[1..].iter().all(|&len| len == candidates));
+
+        assert_eq!(outcome.population.len(), 3);
+    }
+
+    #[test]
+    fn mu_plus_lambda_keeps_the_best() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        // Very aggressive mutation, so that children are pretty much always
+        // worse than their parents
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover,
+            NormalMutation::new(1.0, 10.0).unwrap(),
+        )
+        .with_replacement(Replacement::MuPlusLambda { mu: 2, lambda: 4 });
+
+        let population = vec![
+            individual(&[1.0, 1.0, 1.0]), // fitness = 3.0
+            individual(&[0.0, 0.0, 0.0]), // fitness = 0.0
+            individual(&[1.0, 2.0, 4.0]), // fitness = 7.0
+            individual(&[1.0, 2.0, 1.0]), // fitness = 4.0
+        ];
+
+        let mut population = ga.truncate(population);
+
+        for _ in 0..20 {
+            let best = population[0].clone();
+
+            population = ga.truncate(ga.evolve(&mut rng, &population).unwrap());
+
+            assert_eq!(population.len(), 2);
+            assert!(population[0].fitness() >= best.fitness());
+        }
+    }
+
+    #[test_case(
+        Replacement::Elitism { elites: 5 },
+        crate::Error::PopulationTooSmall { required: 5, actual: 4 }
+        ; "too many elites"
+    )]
+    #[test_case(
+        Replacement::MuCommaLambda { mu: 3, lambda: 2 },
+        crate::Error::InvalidReplacement(Replacement::MuCommaLambda { mu: 3, lambda: 2 })
+        ; "fewer children than parents"
+    )]
+    #[test_case(
+        Replacement::MuPlusLambda { mu: 0, lambda: 2 },
+        crate::Error::InvalidReplacement(Replacement::MuPlusLambda { mu: 0, lambda: 2 })
+        ; "no parents"
+    )]
+    #[test_case(
+        Replacement::SteadyState { replaced: 0 },
+        crate::Error::InvalidReplacement(Replacement::SteadyState { replaced: 0 })
+        ; "nothing replaced"
+    )]
+    fn invalid_replacement(replacement: Replacement, expected: crate::Error) {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover,
+            NormalMutation::new(0.5, 0.5).unwrap(),
+        )
+        .with_replacement(replacement);
+
+        let population = vec![TestIndividual::new(1.0); 4];
+
+        assert_eq!(ga.evolve(&mut rng, &population).unwrap_err(), expected);
+    }
+}
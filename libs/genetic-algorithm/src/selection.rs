@@ -1,12 +1,42 @@
-use crate::chromosome::{Individual, Chromosome};
-use rand::{seq::SliceRandom, Rng, RngCore};
+use crate::{
+    chromosome::{Chromosome, Individual},
+    Error,
+};
+use rand::{
+    distributions::WeightedIndex,
+    seq::{index, SliceRandom},
+    Rng, RngCore,
+};
+use std::cmp::Ordering;
 
 pub trait SelectionMethod {
-    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> Result<&'a I, Error>
     where
         I: Individual;
+
+    /// Selects `count` individuals at once.
+    ///
+    /// By default that's just `count` independent calls to
+    /// [`Self::select()`], but methods that look at the whole batch
+    /// (e.g. [`StochasticUniversalSampling`]) override it.
+    fn select_many<'a, I>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &'a [I],
+        count: usize,
+    ) -> Result<Vec<&'a I>, Error>
+    where
+        I: Individual,
+    {
+        (0..count).map(|_| self.select(rng, population)).collect()
+    }
 }
 
+/// Picks individuals with probability proportional to their fitness.
+///
+/// When everybody's fitness is zero (e.g. the very first generation of
+/// birds, none of which has eaten anything), there's nothing to be
+/// proportional to - so everybody gets the same chance instead.
 pub struct RouletteWheelSelection;
 
 impl RouletteWheelSelection {
@@ -22,23 +52,295 @@ impl Default for RouletteWheelSelection {
 }
 
 impl SelectionMethod for RouletteWheelSelection {
-    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> Result<&'a I, Error>
+    where
+        I: Individual,
+    {
+        if population.is_empty() {
+            return Err(Error::EmptyPopulation);
+        }
+
+        if all_zero(population) {
+            return Ok(uniform(rng, population));
+        }
+
+        Ok(population.choose_weighted(rng, |individual| individual.fitness())?)
+    }
+}
+
+/// Picks `size` random individuals and returns the fittest of them.
+///
+/// Only the *order* of fitnesses matters, so - unlike the roulette wheel -
+/// this works fine when everybody's fitness is zero.
+#[derive(Clone, Debug)]
+pub struct TournamentSelection {
+    size: usize,
+}
+
+impl TournamentSelection {
+    pub fn new(size: usize) -> Result<Self, Error> {
+        if size == 0 {
+            return Err(Error::InvalidTournamentSize(size));
+        }
+
+        Ok(Self { size })
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+}
+
+impl SelectionMethod for TournamentSelection {
+    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> Result<&'a I, Error>
+    where
+        I: Individual,
+    {
+        if population.is_empty() {
+            return Err(Error::EmptyPopulation);
+        }
+
+        Ok((0..self.size)
+            .map(|_| &population[rng.gen_range(0..population.len())])
+            .reduce(|best, individual| {
+                if individual.fitness() > best.fitness() {
+                    individual
+                } else {
+                    best
+                }
+            })
+            .expect("tournament size is never zero"))
+    }
+}
+
+/// Roulette wheel where the n-th worst individual gets a slice of size `n`
+/// instead of a slice proportional to its fitness.
+///
+/// This keeps the selection pressure the same no matter whether fitnesses
+/// are `[0.0, 0.1]` or `[0.0, 1000.0]`.
+#[derive(Clone, Debug)]
+pub struct RankSelection;
+
+impl RankSelection {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for RankSelection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SelectionMethod for RankSelection {
+    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> Result<&'a I, Error>
+    where
+        I: Individual,
+    {
+        if population.is_empty() {
+            return Err(Error::EmptyPopulation);
+        }
+
+        let ranked = by_fitness(population);
+
+        // Ranks are 1, 2, ..., len - so instead of building a
+        // `WeightedIndex`, we can draw a ticket from their sum and walk it
+        let total = ranked.len() * (ranked.len() + 1) / 2;
+        let mut ticket = rng.gen_range(0..total);
+
+        for (rank, individual) in ranked.into_iter().enumerate() {
+            // ---
+            // | worst individual has rank 1, best has rank `len`
+            // ---
+            if ticket <= rank {
+                return Ok(individual);
+            }
+
+            ticket -= rank + 1;
+        }
+
+        unreachable!()
+    }
+}
+
+/// Roulette wheel spun once, with `count` equally spaced pointers.
+///
+/// When selecting many individuals at once, each one is picked either
+/// `floor(expected)` or `ceil(expected)` times - it cannot get unlucky the
+/// way independent spins can. Selecting a single individual is the same as
+/// [`RouletteWheelSelection`] - including the uniform pick when everybody's
+/// fitness is zero.
+#[derive(Clone, Debug)]
+pub struct StochasticUniversalSampling;
+
+impl StochasticUniversalSampling {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for StochasticUniversalSampling {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SelectionMethod for StochasticUniversalSampling {
+    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> Result<&'a I, Error>
+    where
+        I: Individual,
+    {
+        Ok(self.select_many(rng, population, 1)?.remove(0))
+    }
+
+    fn select_many<'a, I>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &'a [I],
+        count: usize,
+    ) -> Result<Vec<&'a I>, Error>
     where
         I: Individual,
     {
-        population
-            .choose_weighted(rng, |individual| individual.fitness())
-            .expect("got an empty population")
+        if population.is_empty() {
+            return Err(Error::EmptyPopulation);
+        }
+
+        if all_zero(population) {
+            return Ok((0..count).map(|_| uniform(rng, population)).collect());
+        }
+
+        // Same validation the roulette wheel does: no negative weights
+        WeightedIndex::new(population.iter().map(|individual| individual.fitness()))?;
+
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+
+        let total: f32 = population
+            .iter()
+            .map(|individual| individual.fitness())
+            .sum();
+        let distance = total / count as f32;
+        let start = rng.gen_range(0.0..distance);
+
+        let mut selected = Vec::with_capacity(count);
+        let mut idx = 0;
+        let mut cumulative = population[0].fitness();
+
+        for pointer in (0..count).map(|i| start + i as f32 * distance) {
+            // Rounding errors can leave the last pointer a tiny bit beyond
+            // the total, so never run past the last individual
+            while cumulative <= pointer && idx + 1 < population.len() {
+                idx += 1;
+                cumulative += population[idx].fitness();
+            }
+
+            selected.push(&population[idx]);
+        }
+
+        // Pointers walk the population in order, so without this the caller
+        // would keep pairing up neighbours
+        selected.shuffle(rng);
+
+        Ok(selected)
     }
 }
 
-pub trait CrossoverMethod {
+/// Picks uniformly among the best `ratio` of the population (e.g. `0.25`
+/// means the top quarter); everybody else never reproduces.
+#[derive(Clone, Debug)]
+pub struct TruncationSelection {
+    ratio: f32,
+}
+
+impl TruncationSelection {
+    pub fn new(ratio: f32) -> Result<Self, Error> {
+        if ratio.is_nan() || ratio <= 0.0 || ratio > 1.0 {
+            return Err(Error::InvalidTruncationRatio(ratio));
+        }
+
+        Ok(Self { ratio })
+    }
+
+    pub fn ratio(&self) -> f32 {
+        self.ratio
+    }
+}
+
+impl SelectionMethod for TruncationSelection {
+    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> Result<&'a I, Error>
+    where
+        I: Individual,
+    {
+        if population.is_empty() {
+            return Err(Error::EmptyPopulation);
+        }
+
+        let ranked = by_fitness(population);
+        let kept = ((ranked.len() as f32 * self.ratio).ceil() as usize).clamp(1, ranked.len());
+
+        Ok(ranked[ranked.len() - kept..][rng.gen_range(0..kept)])
+    }
+}
+
+/// Whether everybody's fitness is zero, in which case fitness-proportionate
+/// methods fall back to [`uniform()`].
+fn all_zero<I>(population: &[I]) -> bool
+where
+    I: Individual,
+{
+    population
+        .iter()
+        .all(|individual| individual.fitness() == 0.0)
+}
+
+/// Picks a random individual, each with the same chance; `population`
+/// must not be empty.
+fn uniform<'a, I>(rng: &mut dyn RngCore, population: &'a [I]) -> &'a I {
+    &population[rng.gen_range(0..population.len())]
+}
+
+/// Returns population sorted from the worst to the best individual.
+///
+/// The sort is stable, so individuals with the same fitness keep their
+/// original order and the results stay reproducible.
+pub(crate) fn by_fitness<I>(population: &[I]) -> Vec<&I>
+where
+    I: Individual,
+{
+    indices_by_fitness(population)
+        .into_iter()
+        .map(|idx| &population[idx])
+        .collect()
+}
+
+/// Like [`by_fitness()`], but returns indices into the population instead
+/// of references - handy when the population is about to be modified.
+pub(crate) fn indices_by_fitness<I>(population: &[I]) -> Vec<usize>
+where
+    I: Individual,
+{
+    let mut indices: Vec<_> = (0..population.len()).collect();
+
+    indices.sort_by(|&a, &b| {
+        population[a]
+            .fitness()
+            .partial_cmp(&population[b].fitness())
+            .unwrap_or(Ordering::Equal)
+    });
+
+    indices
+}
+
+pub trait CrossoverMethod: Send + Sync {
     fn crossover(
         &self,
         rng: &mut dyn RngCore,
         parent_a: &Chromosome,
         parent_b: &Chromosome,
-    ) -> Chromosome;
+    ) -> Result<Chromosome, Error>;
 }
 
 #[derive(Clone, Debug)]
@@ -62,17 +364,301 @@ impl CrossoverMethod for UniformCrossover {
         rng: &mut dyn RngCore,
         parent_a: &Chromosome,
         parent_b: &Chromosome,
-    ) -> Chromosome {
-        assert_eq!(parent_a.len(), parent_b.len());
+    ) -> Result<Chromosome, Error> {
+        ensure_same_length(parent_a, parent_b)?;
 
-        parent_a
+        Ok(parent_a
             .iter()
             .zip(parent_b.iter())
             .map(|(&a, &b)| if rng.gen_bool(0.5) { a } else { b })
-            .collect()
+            .collect())
+    }
+}
+
+/// BLX-α: each gene is drawn uniformly from the range spanned by both
+/// parents' genes, extended by `alpha` times its width on both sides.
+///
+/// - 0.0 = child's genes always lie between parents' genes
+/// - 0.5 = the usual choice, lets the children explore a bit further
+#[derive(Clone, Debug)]
+pub struct BlendCrossover {
+    alpha: f32,
+}
+
+impl BlendCrossover {
+    pub fn new(alpha: f32) -> Result<Self, Error> {
+        if alpha.is_nan() || alpha < 0.0 {
+            return Err(Error::InvalidBlendAlpha(alpha));
+        }
+
+        Ok(Self { alpha })
+    }
+}
+
+impl CrossoverMethod for BlendCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<Chromosome, Error> {
+        ensure_same_length(parent_a, parent_b)?;
+
+        Ok(parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .map(|(&a, &b)| {
+                let extent = self.alpha * (a - b).abs();
+                let min = a.min(b) - extent;
+                let max = a.max(b) + extent;
+
+                // `gen_range()` panics on empty ranges, which is what we'd
+                // get for identical genes
+                if min < max {
+                    rng.gen_range(min..=max)
+                } else {
+                    a
+                }
+            })
+            .collect())
+    }
+}
+
+/// Simulated binary crossover (SBX) - mimics what single-point crossover
+/// does to bit strings, but for real numbers: children are spread
+/// symmetrically around their parents' mean.
+///
+/// `eta` (the distribution index) controls how far from the parents the
+/// children land:
+/// - 0.0 = far away quite often
+/// - 20.0 = mostly very close to one of the parents
+#[derive(Clone, Debug)]
+pub struct SimulatedBinaryCrossover {
+    eta: f32,
+}
+
+impl SimulatedBinaryCrossover {
+    pub fn new(eta: f32) -> Result<Self, Error> {
+        if eta.is_nan() || eta < 0.0 {
+            return Err(Error::InvalidDistributionIndex(eta));
+        }
+
+        Ok(Self { eta })
+    }
+}
+
+impl CrossoverMethod for SimulatedBinaryCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<Chromosome, Error> {
+        ensure_same_length(parent_a, parent_b)?;
+
+        Ok(parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .map(|(&a, &b)| {
+                let u: f32 = rng.gen();
+
+                let beta = if u <= 0.5 {
+                    (2.0 * u).powf(1.0 / (self.eta + 1.0))
+                } else {
+                    (1.0 / (2.0 * (1.0 - u))).powf(1.0 / (self.eta + 1.0))
+                };
+
+                // SBX produces two children - `(1 + beta)` and `(1 - beta)`
+                // are swapped between them - but we need just one, so let's
+                // pick either
+                let (wa, wb) = if rng.gen_bool(0.5) {
+                    (1.0 + beta, 1.0 - beta)
+                } else {
+                    (1.0 - beta, 1.0 + beta)
+                };
+
+                0.5 * (wa * a + wb * b)
+            })
+            .collect())
+    }
+}
+
+/// Takes genes from the first parent up to a random point, and from the
+/// second parent after it.
+#[derive(Clone, Debug)]
+pub struct SinglePointCrossover;
+
+impl SinglePointCrossover {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for SinglePointCrossover {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CrossoverMethod for SinglePointCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<Chromosome, Error> {
+        KPointCrossover { points: 1 }.crossover(rng, parent_a, parent_b)
+    }
+}
+
+/// Cuts both parents at `points` random places and takes the segments
+/// alternately from each of them:
+///
+/// ```text
+/// points = 2
+///
+/// parent a: a a a | a a a a | a a
+/// parent b: b b b | b b b b | b b
+///    child: a a a | b b b b | a a
+/// ```
+#[derive(Clone, Debug)]
+pub struct KPointCrossover {
+    points: usize,
+}
+
+impl KPointCrossover {
+    pub fn new(points: usize) -> Result<Self, Error> {
+        if points == 0 {
+            return Err(Error::InvalidCrossoverPoints(points));
+        }
+
+        Ok(Self { points })
+    }
+}
+
+impl CrossoverMethod for KPointCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<Chromosome, Error> {
+        ensure_same_length(parent_a, parent_b)?;
+
+        // There are `len - 1` places between genes; chromosomes shorter than
+        // `points + 1` simply get cut everywhere
+        let places = parent_a.len().saturating_sub(1);
+        let mut cuts: Vec<_> = index::sample(rng, places, self.points.min(places))
+            .into_iter()
+            .map(|place| place + 1)
+            .collect();
+
+        cuts.sort_unstable();
+
+        let mut cuts = cuts.into_iter().peekable();
+        let mut from_a = true;
+
+        Ok(parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .enumerate()
+            .map(|(idx, (&a, &b))| {
+                if cuts.next_if_eq(&idx).is_some() {
+                    from_a = !from_a;
+                }
+
+                if from_a {
+                    a
+                } else {
+                    b
+                }
+            })
+            .collect())
+    }
+}
+
+/// Uniform crossover over whole neurons instead of single genes - each
+/// neuron's bias and weights are taken together from one of the parents,
+/// so that neurons which work well don't get torn apart.
+///
+/// Expects chromosomes laid out the way `nn::Network::weights()` does it:
+/// layer by layer, neuron by neuron, bias first.
+#[derive(Clone, Debug)]
+pub struct NeuronCrossover {
+    /// Number of genes of each consecutive neuron
+    neurons: Vec<usize>,
+}
+
+impl NeuronCrossover {
+    /// Creates crossover for a network with given number of neurons in each
+    /// layer, input layer included (e.g. `&[9, 18, 2]`).
+    pub fn new(layers: &[usize]) -> Result<Self, Error> {
+        if layers.len() < 2 {
+            return Err(Error::NotEnoughLayers(layers.len()));
+        }
+
+        let neurons = layers
+            .windows(2)
+            .flat_map(|layers| std::iter::repeat_n(layers[0] + 1, layers[1]))
+            .collect();
+
+        Ok(Self { neurons })
+    }
+
+    fn len(&self) -> usize {
+        self.neurons.iter().sum()
+    }
+}
+
+impl CrossoverMethod for NeuronCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Result<Chromosome, Error> {
+        ensure_same_length(parent_a, parent_b)?;
+
+        if parent_a.len() != self.len() {
+            return Err(Error::ChromosomeLengthMismatch {
+                expected: self.len(),
+                actual: parent_a.len(),
+            });
+        }
+
+        let mut genes_a = parent_a.iter();
+        let mut genes_b = parent_b.iter();
+
+        Ok(self
+            .neurons
+            .iter()
+            .flat_map(|&genes| {
+                // Both iterators have to advance, no matter which neuron
+                // we end up taking
+                let neuron_a: Vec<_> = genes_a.by_ref().take(genes).copied().collect();
+                let neuron_b: Vec<_> = genes_b.by_ref().take(genes).copied().collect();
+
+                if rng.gen_bool(0.5) {
+                    neuron_a
+                } else {
+                    neuron_b
+                }
+            })
+            .collect())
     }
 }
 
+fn ensure_same_length(parent_a: &Chromosome, parent_b: &Chromosome) -> Result<(), Error> {
+    if parent_a.len() != parent_b.len() {
+        return Err(Error::ParentLengthMismatch {
+            a: parent_a.len(),
+            b: parent_b.len(),
+        });
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use rand::SeedableRng;
@@ -88,7 +674,9 @@ mod tests {
 
         let parent_b: Chromosome = (1..=100).map(|n| -n as f32).collect();
 
-        let child = UniformCrossover::new().crossover(&mut rng, &parent_a, &parent_b);
+        let child = UniformCrossover::new()
+            .crossover(&mut rng, &parent_a, &parent_b)
+            .unwrap();
 
         // Number of genes different between `child` and `parent_a`
         let diff_a = child.iter().zip(parent_a).filter(|(c, p)| *c != p).count();
@@ -99,4 +687,186 @@ mod tests {
         assert_eq!(diff_a, 49);
         assert_eq!(diff_b, 51);
     }
+
+    fn parents() -> (Chromosome, Chromosome) {
+        (
+            (1..=8).map(|n| n as f32).collect(),
+            (1..=8).map(|n| -n as f32).collect(),
+        )
+    }
+
+    fn crossover(method: &impl CrossoverMethod) -> Vec<f32> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let (parent_a, parent_b) = parents();
+
+        method
+            .crossover(&mut rng, &parent_a, &parent_b)
+            .unwrap()
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn blend() {
+        let child = crossover(&BlendCrossover::new(0.5).unwrap());
+
+        // Gene `n` is somewhere within `-n - n..=n + n`
+        for (n, gene) in (1..=8).zip(&child) {
+            assert!(gene.abs() <= 2.0 * n as f32);
+        }
+
+        approx::assert_relative_eq!(
+            child.as_slice(),
+            [
+                -1.2510376, 2.6953583, 4.908757, 2.1027918, 5.2388067, -6.42202, 0.97117615,
+                -12.237091,
+            ]
+            .as_ref()
+        );
+    }
+
+    #[test]
+    fn blend_without_alpha_stays_between_parents() {
+        let child = crossover(&BlendCrossover::new(0.0).unwrap());
+
+        for (n, gene) in (1..=8).zip(&child) {
+            assert!(gene.abs() <= n as f32);
+        }
+    }
+
+    #[test]
+    fn simulated_binary() {
+        let child = crossover(&SimulatedBinaryCrossover::new(2.0).unwrap());
+
+        approx::assert_relative_eq!(
+            child.as_slice(),
+            [
+                -0.72079206,
+                2.2140012,
+                3.0727615,
+                3.1983404,
+                -5.5669327,
+                4.835296,
+                6.6320076,
+                9.156639,
+            ]
+            .as_ref()
+        );
+    }
+
+    #[test]
+    fn simulated_binary_keeps_identical_genes() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent: Chromosome = vec![0.5, -1.5, 3.0].into_iter().collect();
+
+        let child = SimulatedBinaryCrossover::new(2.0)
+            .unwrap()
+            .crossover(&mut rng, &parent, &parent)
+            .unwrap();
+
+        approx::assert_relative_eq!(
+            child.into_iter().collect::<Vec<_>>().as_slice(),
+            [0.5, -1.5, 3.0].as_ref(),
+        );
+    }
+
+    #[test]
+    fn single_point() {
+        assert_eq!(
+            crossover(&SinglePointCrossover::new()),
+            [1.0, 2.0, -3.0, -4.0, -5.0, -6.0, -7.0, -8.0],
+        );
+    }
+
+    #[test]
+    fn k_point() {
+        assert_eq!(
+            crossover(&KPointCrossover::new(3).unwrap()),
+            [1.0, 2.0, 3.0, 4.0, 5.0, -6.0, 7.0, -8.0],
+        );
+    }
+
+    #[test]
+    fn k_point_with_more_points_than_genes() {
+        // Every place gets cut, so parents simply alternate
+        assert_eq!(
+            crossover(&KPointCrossover::new(100).unwrap()),
+            [1.0, -2.0, 3.0, -4.0, 5.0, -6.0, 7.0, -8.0],
+        );
+    }
+
+    #[test]
+    fn neuron() {
+        // 2 inputs, 1 hidden neuron and 2 outputs, so the chromosome is
+        // made of neurons of 3, 2 and 2 genes
+        let method = NeuronCrossover::new(&[2, 1, 2]).unwrap();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let parent_a: Chromosome = (1..=7).map(|n| n as f32).collect();
+        let parent_b: Chromosome = (1..=7).map(|n| -n as f32).collect();
+
+        let child: Vec<_> = method
+            .crossover(&mut rng, &parent_a, &parent_b)
+            .unwrap()
+            .into_iter()
+            .collect();
+
+        // ---
+        // | hidden neuron and the first output neuron come from parent b,
+        // | the second output neuron from parent a
+        // ---
+        assert_eq!(child, [-1.0, -2.0, -3.0, -4.0, -5.0, 6.0, 7.0]);
+    }
+
+    #[test]
+    fn neuron_rejects_chromosome_of_wrong_length() {
+        let method = NeuronCrossover::new(&[2, 1, 2]).unwrap();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let (parent_a, parent_b) = parents();
+
+        assert_eq!(
+            method
+                .crossover(&mut rng, &parent_a, &parent_b)
+                .unwrap_err(),
+            Error::ChromosomeLengthMismatch {
+                expected: 7,
+                actual: 8
+            },
+        );
+    }
+
+    #[test]
+    fn invalid_crossover_parameters() {
+        assert_eq!(
+            BlendCrossover::new(-0.1).unwrap_err(),
+            Error::InvalidBlendAlpha(-0.1),
+        );
+        assert_eq!(
+            SimulatedBinaryCrossover::new(-1.0).unwrap_err(),
+            Error::InvalidDistributionIndex(-1.0),
+        );
+        assert_eq!(
+            KPointCrossover::new(0).unwrap_err(),
+            Error::InvalidCrossoverPoints(0),
+        );
+        assert_eq!(
+            NeuronCrossover::new(&[3]).unwrap_err(),
+            Error::NotEnoughLayers(1),
+        );
+    }
+
+    #[test]
+    fn crossover_rejects_parents_of_different_length() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let parent_a: Chromosome = vec![1.0, 2.0].into_iter().collect();
+        let parent_b: Chromosome = vec![1.0].into_iter().collect();
+
+        assert_eq!(
+            UniformCrossover::new()
+                .crossover(&mut rng, &parent_a, &parent_b)
+                .unwrap_err(),
+            Error::ParentLengthMismatch { a: 2, b: 1 },
+        );
+    }
 }
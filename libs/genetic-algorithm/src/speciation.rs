@@ -0,0 +1,236 @@
+use crate::{Chromosome, Error, Individual};
+
+/// Clusters population into species - groups of individuals whose
+/// chromosomes lie within `threshold` of each other.
+///
+/// Each species is represented by its first member; an individual joins
+/// the first species whose representative is close enough, or starts a new
+/// one. This is the same (greedy, order-dependent) approach NEAT takes.
+#[derive(Clone, Debug)]
+pub struct Speciation {
+    threshold: f32,
+}
+
+impl Speciation {
+    pub fn new(threshold: f32) -> Result<Self, Error> {
+        if threshold.is_nan() || threshold <= 0.0 {
+            return Err(Error::InvalidCompatibilityThreshold(threshold));
+        }
+
+        Ok(Self { threshold })
+    }
+
+    pub fn threshold(&self) -> f32 {
+        self.threshold
+    }
+
+    /// Returns indices of individuals belonging to each species.
+    pub fn species<I>(&self, population: &[I]) -> Vec<Vec<usize>>
+    where
+        I: Individual,
+    {
+        let mut species: Vec<Vec<usize>> = Vec::new();
+
+        for (idx, individual) in population.iter().enumerate() {
+            let chromosome = individual.chromosome();
+
+            let existing = species.iter_mut().find(|members| {
+                chromosome.distance(population[members[0]].chromosome()) <= self.threshold
+            });
+
+            match existing {
+                Some(members) => members.push(idx),
+                None => species.push(vec![idx]),
+            }
+        }
+
+        species
+    }
+
+    /// Returns population with each individual's fitness divided by the
+    /// size of its species.
+    pub(crate) fn share<'a, I>(&self, population: &'a [I]) -> Vec<Shared<'a, I>>
+    where
+        I: Individual,
+    {
+        let mut shared: Vec<_> = population
+            .iter()
+            .map(|individual| Shared {
+                individual,
+                fitness: individual.fitness(),
+            })
+            .collect();
+
+        for members in self.species(population) {
+            for &idx in &members {
+                shared[idx].fitness /= members.len() as f32;
+            }
+        }
+
+        shared
+    }
+}
+
+/// Individual as seen by the selection method when fitness sharing is on.
+pub(crate) struct Shared<'a, I> {
+    individual: &'a I,
+    fitness: f32,
+}
+
+impl<I> Individual for Shared<'_, I>
+where
+    I: Individual,
+{
+    fn create(_: Chromosome) -> Self {
+        unreachable!("shared individuals are only ever selected, never created")
+    }
+
+    fn fitness(&self) -> f32 {
+        self.fitness
+    }
+
+    fn chromosome(&self) -> &Chromosome {
+        self.individual.chromosome()
+    }
+}
+
+/// Mean distance between chromosomes of all pairs of individuals:
+/// - 0.0 = everybody's the same
+/// - the higher, the more diverse the population
+pub fn diversity<I>(population: &[I]) -> f32
+where
+    I: Individual,
+{
+    let mut total = 0.0;
+    let mut pairs = 0;
+
+    for (idx, a) in population.iter().enumerate() {
+        for b in &population[idx + 1..] {
+            total += a.chromosome().distance(b.chromosome());
+            pairs += 1;
+        }
+    }
+
+    if pairs == 0 {
+        0.0
+    } else {
+        total / pairs as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::chromosome::tests::histogram::{individual, population, TestIndividual};
+    use crate::{
+        GeneticAlgorithm, NormalMutation, RouletteWheelSelection, SelectionMethod,
+        TournamentSelection, UniformCrossover,
+    };
+
+    #[test]
+    fn speciation() {
+        let population = vec![
+            individual(&[0.0, 0.0]),
+            individual(&[5.0, 5.0]),
+            individual(&[0.5, 0.0]),
+            individual(&[5.0, 4.0]),
+            individual(&[9.0, 9.0]),
+        ];
+
+        assert_eq!(
+            Speciation::new(1.0).unwrap().species(&population),
+            [vec![0, 2], vec![1, 3], vec![4]],
+        );
+
+        // Big enough threshold puts everybody into the same species
+        assert_eq!(
+            Speciation::new(100.0).unwrap().species(&population),
+            [vec![0, 1, 2, 3, 4]],
+        );
+    }
+
+    #[test]
+    fn fitness_sharing() {
+        let method = RouletteWheelSelection::new();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        // Three clones of fitness 3.0 and a loner of fitness 2.0
+        let population = vec![
+            individual(&[1.0, 1.0, 1.0]),
+            individual(&[1.0, 1.0, 1.0]),
+            individual(&[1.0, 1.0, 1.0]),
+            individual(&[0.0, 0.0, 2.0]),
+        ];
+
+        let shared = Speciation::new(0.5).unwrap().share(&population);
+
+        let actual_histogram: BTreeMap<i32, _> = (0..1000)
+            .map(|_| method.select(&mut rng, &shared).unwrap())
+            .fold(Default::default(), |mut histogram, individual| {
+                *histogram
+                    .entry(individual.chromosome()[2] as _)
+                    .or_default() += 1;
+
+                histogram
+            });
+
+        // ---
+        // | without sharing the loner would get picked only 2/11 of the
+        // | time - with sharing clones have 1.0 each, so it's 2/5
+        // ---
+        let expected_histogram = maplit::btreemap! {
+            1 => 601,
+            2 => 399,
+        };
+
+        assert_eq!(expected_histogram, actual_histogram);
+    }
+
+    #[test]
+    fn evolve_with_fitness_sharing() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            TournamentSelection::new(2).unwrap(),
+            UniformCrossover,
+            NormalMutation::new(0.5, 0.5).unwrap(),
+        )
+        .with_fitness_sharing(Speciation::new(1.0).unwrap());
+
+        let population = population(&mut rng);
+        let evolved = ga.evolve(&mut rng, &population).unwrap();
+
+        assert_eq!(evolved.len(), population.len());
+    }
+
+    #[test]
+    fn test_diversity() {
+        let same = vec![individual(&[1.0, 2.0]); 3];
+
+        let different = vec![
+            individual(&[0.0, 0.0]),
+            individual(&[3.0, 4.0]),
+            individual(&[0.0, 4.0]),
+        ];
+
+        approx::assert_relative_eq!(diversity(&same), 0.0);
+
+        // (5.0 + 4.0 + 3.0) / 3
+        approx::assert_relative_eq!(diversity(&different), 4.0);
+
+        approx::assert_relative_eq!(diversity::<TestIndividual>(&[]), 0.0);
+    }
+
+    #[test]
+    fn invalid_compatibility_threshold() {
+        assert!(matches!(
+            Speciation::new(0.0),
+            Err(crate::Error::InvalidCompatibilityThreshold(_)),
+        ));
+    }
+}
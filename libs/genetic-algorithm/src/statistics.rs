@@ -0,0 +1,111 @@
+use crate::{diversity, Error, Individual};
+
+/// Summary of a single, already evaluated, generation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Statistics {
+    /// Index of the generation these statistics describe, starting at 0
+    /// (the initial population).
+    pub generation: usize,
+    pub min_fitness: f32,
+    pub max_fitness: f32,
+    pub mean_fitness: f32,
+    pub median_fitness: f32,
+    pub stddev_fitness: f32,
+
+    /// See [`diversity()`].
+    pub diversity: f32,
+}
+
+impl Statistics {
+    pub fn new<I>(generation: usize, population: &[I]) -> Result<Self, Error>
+    where
+        I: Individual,
+    {
+        let fitnesses = population.iter().map(|individual| individual.fitness());
+
+        Ok(Self {
+            diversity: diversity(population),
+            ..Self::from_fitnesses(generation, fitnesses)?
+        })
+    }
+
+    /// Summarizes bare numbers - handy when the interesting number is not
+    /// the fitness itself (e.g. it's been adjusted by novelty).
+    ///
+    /// Numbers alone don't tell how different individuals are, so
+    /// `diversity` is left at zero.
+    pub fn from_fitnesses(
+        generation: usize,
+        fitnesses: impl IntoIterator<Item = f32>,
+    ) -> Result<Self, Error> {
+        let mut fitnesses: Vec<_> = fitnesses.into_iter().collect();
+
+        if fitnesses.is_empty() {
+            return Err(Error::EmptyPopulation);
+        }
+
+        fitnesses.sort_by(|a, b| a.total_cmp(b));
+
+        let len = fitnesses.len() as f32;
+        let mean = fitnesses.iter().sum::<f32>() / len;
+
+        let median = if fitnesses.len() % 2 == 0 {
+            let mid = fitnesses.len() / 2;
+            (fitnesses[mid - 1] + fitnesses[mid]) / 2.0
+        } else {
+            fitnesses[fitnesses.len() / 2]
+        };
+
+        let variance = fitnesses
+            .iter()
+            .map(|fitness| (fitness - mean).powi(2))
+            .sum::<f32>()
+            / len;
+
+        Ok(Self {
+            generation,
+            min_fitness: fitnesses[0],
+            max_fitness: fitnesses[fitnesses.len() - 1],
+            mean_fitness: mean,
+            median_fitness: median,
+            stddev_fitness: variance.sqrt(),
+            diversity: 0.0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn odd_population() {
+        let actual = Statistics::from_fitnesses(3, [4.0, 1.0, 7.0]).unwrap();
+
+        assert_eq!(actual.generation, 3);
+        approx::assert_relative_eq!(actual.min_fitness, 1.0);
+        approx::assert_relative_eq!(actual.max_fitness, 7.0);
+        approx::assert_relative_eq!(actual.mean_fitness, 4.0);
+        approx::assert_relative_eq!(actual.median_fitness, 4.0);
+        approx::assert_relative_eq!(actual.stddev_fitness, 6.0_f32.sqrt());
+    }
+
+    #[test]
+    fn even_population() {
+        let actual = Statistics::from_fitnesses(0, [2.0, 8.0, 4.0, 2.0]).unwrap();
+
+        approx::assert_relative_eq!(actual.min_fitness, 2.0);
+        approx::assert_relative_eq!(actual.max_fitness, 8.0);
+        approx::assert_relative_eq!(actual.mean_fitness, 4.0);
+        approx::assert_relative_eq!(actual.median_fitness, 3.0);
+        approx::assert_relative_eq!(actual.stddev_fitness, 6.0_f32.sqrt());
+    }
+
+    #[test]
+    fn empty_population() {
+        assert_eq!(
+            Statistics::from_fitnesses(0, []).unwrap_err(),
+            Error::EmptyPopulation
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+
+/// Function applied to a neuron's output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Activation {
+    /// `max(0, x)`
+    #[default]
+    #[serde(rename = "relu")]
+    ReLU,
+
+    /// `x` for positive numbers, `0.01 * x` otherwise - like ReLU, but
+    /// doesn't completely kill negative signals
+    #[serde(rename = "leaky_relu")]
+    LeakyReLU,
+
+    /// `1 / (1 + e^-x)`, squashes everything into `(0, 1)`
+    Sigmoid,
+
+    /// Squashes everything into `(-1, 1)`
+    Tanh,
+
+    /// `x`, passes numbers through untouched
+    Identity,
+
+    /// `x / (1 + |x|)`, like `Tanh`, but cheaper and with softer tails
+    Softsign,
+}
+
+impl Activation {
+    const LEAKY_RELU_SLOPE: f32 = 0.01;
+
+    pub fn apply(self, x: f32) -> f32 {
+        match self {
+            Self::ReLU => x.max(0.0),
+            Self::LeakyReLU => {
+                if x > 0.0 {
+                    x
+                } else {
+                    Self::LEAKY_RELU_SLOPE * x
+                }
+            }
+            Self::Sigmoid => 1.0 / (1.0 + (-x).exp()),
+            Self::Tanh => x.tanh(),
+            Self::Identity => x,
+            Self::Softsign => x / (1.0 + x.abs()),
+        }
+    }
+
+    /// Whether this function can return negative numbers as readily as
+    /// positive ones.
+    pub fn is_symmetric(self) -> bool {
+        matches!(self, Self::Tanh | Self::Identity | Self::Softsign)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(Activation::ReLU, -2.0, 0.0)]
+    #[test_case(Activation::ReLU, 2.0, 2.0)]
+    #[test_case(Activation::LeakyReLU, -2.0, -0.02)]
+    #[test_case(Activation::LeakyReLU, 2.0, 2.0)]
+    #[test_case(Activation::Sigmoid, 0.0, 0.5)]
+    #[test_case(Activation::Sigmoid, 2.0, 0.880797)]
+    #[test_case(Activation::Tanh, -2.0, -0.9640276)]
+    #[test_case(Activation::Tanh, 0.5, 0.46211717)]
+    #[test_case(Activation::Identity, -2.0, -2.0)]
+    #[test_case(Activation::Softsign, -3.0, -0.75)]
+    #[test_case(Activation::Softsign, 1.0, 0.5)]
+    fn apply(activation: Activation, x: f32, expected: f32) {
+        approx::assert_relative_eq!(activation.apply(x), expected);
+    }
+
+    #[test]
+    fn serialization() {
+        let names: Vec<_> = [
+            Activation::ReLU,
+            Activation::LeakyReLU,
+            Activation::Sigmoid,
+            Activation::Tanh,
+            Activation::Identity,
+            Activation::Softsign,
+        ]
+        .iter()
+        .map(|activation| serde_json::to_string(activation).unwrap())
+        .collect();
+
+        assert_eq!(
+            names,
+            [
+                r#""relu""#,
+                r#""leaky_relu""#,
+                r#""sigmoid""#,
+                r#""tanh""#,
+                r#""identity""#,
+                r#""softsign""#,
+            ]
+        );
+    }
+}
@@ -0,0 +1,38 @@
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum Error {
+    #[error("network needs at least two layers, got {0}")]
+    NotEnoughLayers(usize),
+
+    #[error("topology expects {expected} weights in total, got {actual}")]
+    TotalWeightCountMismatch { expected: usize, actual: usize },
+
+    #[error("network expects {expected} inputs, got {actual}")]
+    InputCountMismatch { expected: usize, actual: usize },
+
+    #[error("topology describes {expected} layers of neurons, got {actual}")]
+    LayerCountMismatch { expected: usize, actual: usize },
+
+    #[error("layer {layer} has {expected} neurons, but got {actual} biases")]
+    BiasCountMismatch {
+        layer: usize,
+        expected: usize,
+        actual: usize,
+    },
+
+    #[error("layer {layer} has {expected} neurons, but got weights for {actual}")]
+    NeuronCountMismatch {
+        layer: usize,
+        expected: usize,
+        actual: usize,
+    },
+
+    #[error("neuron {neuron} of layer {layer} expects {expected} weights, got {actual}")]
+    WeightCountMismatch {
+        layer: usize,
+        neuron: usize,
+        expected: usize,
+        actual: usize,
+    },
+}
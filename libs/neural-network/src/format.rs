@@ -0,0 +1,256 @@
+//! Self-describing, serde-friendly representation of a [`Network`].
+//!
+//! ```json
+//! {
+//!   "topology": [2, 1],
+//!   "layers": [
+//!     { "activation": "relu", "biases": [0.5], "weights": [[-0.3, 0.8]] }
+//!   ]
+//! }
+//! ```
+//!
+//! `weights[n]` holds the incoming weights of the `n`-th neuron, so each
+//! layer is a `neurons x inputs` matrix.
+
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct NetworkFormat {
+    topology: Vec<usize>,
+    layers: Vec<LayerFormat>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct LayerFormat {
+    activation: Activation,
+    biases: Vec<f32>,
+    weights: Vec<Vec<f32>>,
+}
+
+impl From<Network> for NetworkFormat {
+    fn from(network: Network) -> Self {
+        let topology = network
+            .topology()
+            .into_iter()
+            .map(|layer| layer.neurons)
+            .collect();
+
+        let layers = network
+            .layers
+            .into_iter()
+            .map(|layer| {
+                let (biases, weights) = layer
+                    .neurons
+                    .into_iter()
+                    .map(|neuron| (neuron.bias, neuron.weights))
+                    .unzip();
+
+                LayerFormat {
+                    activation: layer.activation,
+                    biases,
+                    weights,
+                }
+            })
+            .collect();
+
+        Self { topology, layers }
+    }
+}
+
+impl TryFrom<NetworkFormat> for Network {
+    type Error = Error;
+
+    fn try_from(format: NetworkFormat) -> Result<Self, Self::Error> {
+        let NetworkFormat { topology, layers } = format;
+
+        if topology.len() < 2 {
+            return Err(Error::NotEnoughLayers(topology.len()));
+        }
+
+        if layers.len() != topology.len() - 1 {
+            return Err(Error::LayerCountMismatch {
+                expected: topology.len() - 1,
+                actual: layers.len(),
+            });
+        }
+
+        let layers = layers
+            .into_iter()
+            .zip(topology.windows(2))
+            .enumerate()
+            .map(|(layer_idx, (layer, sizes))| {
+                let (inputs, neurons) = (sizes[0], sizes[1]);
+
+                if layer.biases.len() != neurons {
+                    return Err(Error::BiasCountMismatch {
+                        layer: layer_idx,
+                        expected: neurons,
+                        actual: layer.biases.len(),
+                    });
+                }
+
+                if layer.weights.len() != neurons {
+                    return Err(Error::NeuronCountMismatch {
+                        layer: layer_idx,
+                        expected: neurons,
+                        actual: layer.weights.len(),
+                    });
+                }
+
+                let neurons = layer
+                    .biases
+                    .into_iter()
+                    .zip(layer.weights)
+                    .enumerate()
+                    .map(|(neuron_idx, (bias, weights))| {
+                        if weights.len() != inputs {
+                            return Err(Error::WeightCountMismatch {
+                                layer: layer_idx,
+                                neuron: neuron_idx,
+                                expected: inputs,
+                                actual: weights.len(),
+                            });
+                        }
+
+                        Ok(Neuron { bias, weights })
+                    })
+                    .collect::<Result<_, _>>()?;
+
+                Ok(Layer {
+                    activation: layer.activation,
+                    neurons,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { layers })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn network() -> Network {
+        Network::new(vec![
+            Layer::new(
+                Activation::ReLU,
+                vec![
+                    Neuron::new(0.1, vec![0.2, 0.3]),
+                    Neuron::new(0.4, vec![0.5, 0.6]),
+                ],
+            ),
+            Layer::new(Activation::Tanh, vec![Neuron::new(0.7, vec![0.8, 0.9])]),
+        ])
+    }
+
+    fn load(json: &str) -> Result<Network, String> {
+        serde_json::from_str(json).map_err(|err| err.to_string())
+    }
+
+    #[test]
+    fn serialize() {
+        let actual = serde_json::to_value(network()).unwrap();
+
+        let expected = serde_json::json!({
+            "topology": [2, 2, 1],
+            "layers": [
+                {
+                    "activation": "relu",
+                    "biases": [0.1_f32, 0.4_f32],
+                    "weights": [[0.2_f32, 0.3_f32], [0.5_f32, 0.6_f32]],
+                },
+                {
+                    "activation": "tanh",
+                    "biases": [0.7_f32],
+                    "weights": [[0.8_f32, 0.9_f32]],
+                },
+            ],
+        });
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let network = Network::random(
+            &mut rng,
+            &[
+                LayerTopology::new(3),
+                LayerTopology::new(5).with_activation(Activation::Sigmoid),
+                LayerTopology::new(2).with_activation(Activation::Softsign),
+            ],
+        );
+
+        let json = serde_json::to_string(&network).unwrap();
+        let restored: Network = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored, network);
+    }
+
+    #[test]
+    fn not_enough_layers() {
+        let err = load(r#"{ "topology": [2], "layers": [] }"#).unwrap_err();
+
+        assert!(err.starts_with(&Error::NotEnoughLayers(1).to_string()));
+    }
+
+    #[test]
+    fn layer_count_mismatch() {
+        let err = load(r#"{ "topology": [1, 1, 1], "layers": [] }"#).unwrap_err();
+
+        assert!(err.starts_with(
+            &Error::LayerCountMismatch {
+                expected: 2,
+                actual: 0
+            }
+            .to_string()
+        ));
+    }
+
+    #[test]
+    fn bias_count_mismatch() {
+        let err = load(
+            r#"{
+                "topology": [1, 2],
+                "layers": [{ "activation": "relu", "biases": [0.1], "weights": [[0.2], [0.3]] }]
+            }"#,
+        )
+        .unwrap_err();
+
+        assert!(err.starts_with(
+            &Error::BiasCountMismatch {
+                layer: 0,
+                expected: 2,
+                actual: 1
+            }
+            .to_string()
+        ));
+    }
+
+    #[test]
+    fn weight_count_mismatch() {
+        let err = load(
+            r#"{
+                "topology": [2, 1],
+                "layers": [{ "activation": "relu", "biases": [0.1], "weights": [[0.2]] }]
+            }"#,
+        )
+        .unwrap_err();
+
+        assert!(err.starts_with(
+            &Error::WeightCountMismatch {
+                layer: 0,
+                neuron: 0,
+                expected: 2,
+                actual: 1
+            }
+            .to_string()
+        ));
+    }
+}
@@ -1,13 +1,42 @@
+mod activation;
+mod error;
+mod format;
+
+pub use activation::*;
+pub use error::*;
+
 /********************
 *  Network
 *********************/
+use format::NetworkFormat;
 use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct LayerTopology {
     pub neurons: usize,
+
+    /// Activation of this layer's neurons; ignored for the input layer,
+    /// which just passes its numbers through.
+    pub activation: Activation,
+}
+
+impl LayerTopology {
+    /// Creates a layer of ReLU neurons.
+    pub fn new(neurons: usize) -> Self {
+        Self {
+            neurons,
+            activation: Activation::ReLU,
+        }
+    }
+
+    pub fn with_activation(self, activation: Activation) -> Self {
+        Self { activation, ..self }
+    }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "NetworkFormat", into = "NetworkFormat")]
 pub struct Network {
     layers: Vec<Layer>,
 }
@@ -18,9 +47,37 @@ impl Network {
         Self { layers }
     }
 
-    // pub fn from_weights(layers: &[LayerTopology], weights: impl IntoIterator<Item = 32>) -> Self {
-    //     todo!()
-    // }
+    pub fn from_weights(
+        layers: &[LayerTopology],
+        weights: impl IntoIterator<Item = f32>,
+    ) -> Result<Self, Error> {
+        if layers.len() < 2 {
+            return Err(Error::NotEnoughLayers(layers.len()));
+        }
+
+        let expected = layers
+            .windows(2)
+            .map(|layers| (layers[0].neurons + 1) * layers[1].neurons)
+            .sum();
+
+        let weights: Vec<_> = weights.into_iter().collect();
+
+        if weights.len() != expected {
+            return Err(Error::TotalWeightCountMismatch {
+                expected,
+                actual: weights.len(),
+            });
+        }
+
+        let mut weights = weights.into_iter();
+
+        let layers = layers
+            .windows(2)
+            .map(|layers| Layer::from_weights(layers[0].neurons, layers[1], &mut weights))
+            .collect();
+
+        Ok(Self { layers })
+    }
 
     pub fn random(rng: &mut dyn RngCore, layers: &[LayerTopology]) -> Self {
         Self {
@@ -28,18 +85,36 @@ impl Network {
                 .windows(2)
                 .map(|layers| {
                     let input_neurons = layers[0].neurons;
-                    let output_neurons = layers[1].neurons;
 
-                    Layer::random(rng, input_neurons, output_neurons)
+                    Layer::random(rng, input_neurons, layers[1])
                 })
                 .collect(),
         }
     }
 
-    pub fn propagate(&self, inputs: Vec<f32>) -> Vec<f32> {
+    pub fn propagate(&self, inputs: Vec<f32>) -> Result<Vec<f32>, Error> {
         self.layers
             .iter()
-            .fold(inputs, |inputs, layer| layer.propagate(inputs))
+            .try_fold(inputs, |inputs, layer| layer.propagate(inputs))
+    }
+
+    pub fn topology(&self) -> Vec<LayerTopology> {
+        let inputs = self.layers.first().map(|layer| {
+            LayerTopology::new(
+                layer
+                    .neurons
+                    .first()
+                    .map_or(0, |neuron| neuron.weights.len()),
+            )
+        });
+
+        inputs
+            .into_iter()
+            .chain(self.layers.iter().map(|layer| LayerTopology {
+                neurons: layer.neurons.len(),
+                activation: layer.activation,
+            }))
+            .collect()
     }
 
     pub fn weights(&self) -> Vec<f32> {
@@ -57,29 +132,47 @@ impl Network {
 *  Layer
 *********************/
 
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq)]
 struct Layer {
+    activation: Activation,
     neurons: Vec<Neuron>,
 }
 
 impl Layer {
     #[cfg(test)]
-    fn new(neurons: Vec<Neuron>) -> Self {
-        Self { neurons }
+    fn new(activation: Activation, neurons: Vec<Neuron>) -> Self {
+        Self {
+            activation,
+            neurons,
+        }
     }
 
-    fn random(rng: &mut dyn RngCore, input_neurons: usize, output_neurons: usize) -> Self {
+    fn random(rng: &mut dyn RngCore, input_neurons: usize, output: LayerTopology) -> Self {
         Self {
-            neurons: (0..output_neurons)
+            activation: output.activation,
+            neurons: (0..output.neurons)
                 .map(|_| Neuron::random(rng, input_neurons))
                 .collect(),
         }
     }
 
-    fn propagate(&self, inputs: Vec<f32>) -> Vec<f32> {
+    fn from_weights(
+        input_neurons: usize,
+        output: LayerTopology,
+        weights: &mut dyn Iterator<Item = f32>,
+    ) -> Self {
+        Self {
+            activation: output.activation,
+            neurons: (0..output.neurons)
+                .map(|_| Neuron::from_weights(input_neurons, weights))
+                .collect(),
+        }
+    }
+
+    fn propagate(&self, inputs: Vec<f32>) -> Result<Vec<f32>, Error> {
         self.neurons
             .iter()
-            .map(|neuron| neuron.propagate(&inputs))
+            .map(|neuron| neuron.propagate(self.activation, &inputs))
             .collect()
     }
 }
@@ -87,7 +180,7 @@ impl Layer {
 /********************
 *  Neuron
 *********************/
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq)]
 struct Neuron {
     bias: f32,
     weights: Vec<f32>,
@@ -108,15 +201,32 @@ impl Neuron {
         }
     }
 
-    fn propagate(&self, inputs: &[f32]) -> f32 {
-        // TODO: use Result instead of assert
-        assert_eq!(inputs.len(), self.weights.len());
+    /// Caller must ensure there are enough weights - see
+    /// [`Network::from_weights()`].
+    fn from_weights(input_neurons: usize, weights: &mut dyn Iterator<Item = f32>) -> Self {
+        let mut weights = weights.take(input_neurons + 1);
+        let bias = weights.next().unwrap_or_default();
 
-        inputs
+        Self {
+            bias,
+            weights: weights.collect(),
+        }
+    }
+
+    fn propagate(&self, activation: Activation, inputs: &[f32]) -> Result<f32, Error> {
+        if inputs.len() != self.weights.len() {
+            return Err(Error::InputCountMismatch {
+                expected: self.weights.len(),
+                actual: inputs.len(),
+            });
+        }
+
+        let output = inputs
             .iter()
             .zip(&self.weights)
-            .fold(self.bias, |acc, (input, weight)| acc + input * weight)
-            .max(0.0)
+            .fold(self.bias, |acc, (input, weight)| acc + input * weight);
+
+        Ok(activation.apply(output))
     }
 }
 
@@ -134,9 +244,9 @@ mod tests {
             let network = Network::random(
                 &mut rng,
                 &[
-                    LayerTopology { neurons: 1 },
-                    LayerTopology { neurons: 2 },
-                    LayerTopology { neurons: 3 },
+                    LayerTopology::new(1),
+                    LayerTopology::new(2),
+                    LayerTopology::new(3),
                 ],
             );
             assert_eq!(network.layers.len(), 2);
@@ -145,13 +255,115 @@ mod tests {
         #[test]
         fn test_weigths() {
             let network = Network::new(vec![
-                Layer::new(vec![Neuron::new(0.1, vec![0.2, 0.3, 0.4])]),
-                Layer::new(vec![Neuron::new(0.5, vec![0.6, 0.7, 0.8])]),
+                Layer::new(
+                    Activation::ReLU,
+                    vec![Neuron::new(0.1, vec![0.2, 0.3, 0.4])],
+                ),
+                Layer::new(
+                    Activation::ReLU,
+                    vec![Neuron::new(0.5, vec![0.6, 0.7, 0.8])],
+                ),
             ]);
             let actual = network.weights();
             let expected = vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8];
             approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
         }
+
+        #[test]
+        fn test_from_weights() {
+            let layers = &[LayerTopology::new(3), LayerTopology::new(2)];
+            let weights = vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8];
+
+            let network = Network::from_weights(layers, weights.clone()).unwrap();
+            let actual: Vec<f32> = network.weights();
+
+            approx::assert_relative_eq!(actual.as_slice(), weights.as_slice());
+        }
+
+        #[test]
+        fn test_weights_round_trip() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let layers = &[
+                LayerTopology::new(3),
+                LayerTopology::new(6),
+                LayerTopology::new(2),
+            ];
+
+            let network = Network::random(&mut rng, layers);
+            let restored = Network::from_weights(layers, network.weights()).unwrap();
+
+            assert_eq!(network.weights(), restored.weights());
+            assert_eq!(
+                network.propagate(vec![0.5, -0.25, 1.0]),
+                restored.propagate(vec![0.5, -0.25, 1.0]),
+            );
+        }
+
+        #[test]
+        fn test_topology() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let network = Network::random(
+                &mut rng,
+                &[
+                    LayerTopology::new(3),
+                    LayerTopology::new(6),
+                    LayerTopology::new(2),
+                ],
+            );
+
+            let actual: Vec<_> = network.topology().iter().map(|l| l.neurons).collect();
+            assert_eq!(actual, [3, 6, 2]);
+        }
+
+        #[test]
+        fn test_from_weights_not_enough() {
+            let layers = &[LayerTopology::new(3), LayerTopology::new(2)];
+
+            assert_eq!(
+                Network::from_weights(layers, vec![0.1, 0.2, 0.3]),
+                Err(Error::TotalWeightCountMismatch {
+                    expected: 8,
+                    actual: 3
+                }),
+            );
+        }
+
+        #[test]
+        fn test_from_weights_too_many() {
+            let layers = &[LayerTopology::new(1), LayerTopology::new(1)];
+
+            assert_eq!(
+                Network::from_weights(layers, vec![0.1, 0.2, 0.3]),
+                Err(Error::TotalWeightCountMismatch {
+                    expected: 2,
+                    actual: 3
+                }),
+            );
+        }
+
+        #[test]
+        fn test_from_weights_not_enough_layers() {
+            let layers = &[LayerTopology::new(1)];
+
+            assert_eq!(
+                Network::from_weights(layers, vec![]),
+                Err(Error::NotEnoughLayers(1)),
+            );
+        }
+
+        #[test]
+        fn test_propagate_wrong_input_count() {
+            let layers = &[LayerTopology::new(2), LayerTopology::new(1)];
+            let network = Network::from_weights(layers, vec![0.1, 0.2, 0.3]).unwrap();
+
+            assert_eq!(
+                network.propagate(vec![1.0]),
+                Err(Error::InputCountMismatch {
+                    expected: 2,
+                    actual: 1
+                }),
+            );
+        }
     }
 
     mod layer {
@@ -159,7 +371,7 @@ mod tests {
         #[test]
         fn test() {
             let mut rng = ChaCha8Rng::from_seed(Default::default());
-            let layer = Layer::random(&mut rng, 3, 2);
+            let layer = Layer::random(&mut rng, 3, LayerTopology::new(2));
 
             approx::assert_relative_eq!(layer.neurons[0].bias, -0.6255188);
             approx::assert_relative_eq!(
@@ -204,16 +416,33 @@ mod tests {
             };
 
             // Ensures `.max()` (our ReLU) works:
-            approx::assert_relative_eq!(neuron.propagate(&[-10.0, -10.0]), 0.0,);
+            approx::assert_relative_eq!(
+                neuron.propagate(Activation::ReLU, &[-10.0, -10.0]).unwrap(),
+                0.0,
+            );
 
             // `0.5` and `1.0` chosen by a fair dice roll:
             approx::assert_relative_eq!(
-                neuron.propagate(&[0.5, 1.0]),
+                neuron.propagate(Activation::ReLU, &[0.5, 1.0]).unwrap(),
                 (-0.3 * 0.5) + (0.8 * 1.0) + 0.5,
             );
 
             // We could've written `1.15` right away, but showing the entire
             // formula makes our intentions clearer
         }
+
+        #[test]
+        fn test_symmetric_activation() {
+            let neuron = Neuron {
+                bias: 0.5,
+                weights: vec![-0.3, 0.8],
+            };
+
+            // Unlike ReLU, tanh lets negative signals through:
+            approx::assert_relative_eq!(
+                neuron.propagate(Activation::Tanh, &[1.0, -1.0]).unwrap(),
+                (-0.3_f32 - 0.8 + 0.5).tanh(),
+            );
+        }
     }
 }
@@ -6,18 +6,67 @@ use serde_wasm_bindgen as swb;
 
 #[wasm_bindgen]
 pub struct Simulation {
-    rng: ThreadRng,
     sim: sim::Simulation,
 }
 
 #[wasm_bindgen]
 impl Simulation {
     #[wasm_bindgen(constructor)]
-    pub fn new() -> Self {
-        let mut rng = thread_rng();
-        let sim = sim::Simulation::random(&mut rng);
+    pub fn new() -> Result<Simulation, JsValue> {
+        Self::from_config(sim::Config::default())
+    }
+
+    /// Creates a simulation from a (possibly partial) config object; see
+    /// `defaultConfig()` for the available fields.
+    #[wasm_bindgen(js_name = withConfig)]
+    pub fn with_config(config: JsValue) -> Result<Simulation, JsValue> {
+        let config: sim::Config = swb::from_value(config)?;
+
+        Self::from_config(config)
+    }
+
+    /// Creates a simulation out of a population trained offline with
+    /// `shorelark-cli`.
+    #[wasm_bindgen(js_name = fromPopulation)]
+    pub fn from_population(population: JsValue) -> Result<Simulation, JsValue> {
+        let population: sim::Population = swb::from_value(population)?;
+        let sim = sim::Simulation::from_population(thread_rng().gen(), population)
+            .map_err(to_js_error)?;
+
+        Ok(Self { sim })
+    }
+
+    /// Restores a simulation saved with `save()`.
+    pub fn load(json: &str) -> Result<Simulation, JsValue> {
+        let sim = sim::Simulation::from_json(json).map_err(to_js_error)?;
+
+        Ok(Self { sim })
+    }
+
+    /// Restores a simulation saved with `saveBytes()`.
+    #[wasm_bindgen(js_name = loadBytes)]
+    pub fn load_bytes(bytes: &[u8]) -> Result<Simulation, JsValue> {
+        let sim = sim::Simulation::from_bytes(bytes).map_err(to_js_error)?;
+
+        Ok(Self { sim })
+    }
 
-        Self { rng, sim }
+    pub fn save(&self) -> Result<String, JsValue> {
+        self.sim.to_json().map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = saveBytes)]
+    pub fn save_bytes(&self) -> Result<Vec<u8>, JsValue> {
+        self.sim.to_bytes().map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = defaultConfig)]
+    pub fn default_config() -> JsValue {
+        swb::to_value(&sim::Config::default()).unwrap()
+    }
+
+    pub fn config(&self) -> JsValue {
+        swb::to_value(self.sim.config()).unwrap()
     }
 
     pub fn world(&self) -> JsValue {
@@ -26,15 +75,56 @@ impl Simulation {
         swb::to_value(&world).unwrap()
     }
 
-    pub fn step(&mut self) {
-        self.sim.step(&mut self.rng);
+    pub fn step(&mut self) -> Result<(), JsValue> {
+        self.sim.step().map_err(to_js_error)?;
+
+        Ok(())
+    }
+
+    /// Fast-forwards until the end of the current generation and returns
+    /// its statistics.
+    pub fn train(&mut self) -> Result<JsValue, JsValue> {
+        let statistics = self.sim.train().map_err(to_js_error)?;
+
+        Ok(swb::to_value(&statistics)?)
+    }
+
+    /// Statistics of the most recently evolved generation, or `null`.
+    pub fn statistics(&self) -> JsValue {
+        swb::to_value(&self.sim.statistics()).unwrap()
+    }
+
+    /// The best birds of all generations so far, best first.
+    #[wasm_bindgen(js_name = hallOfFame)]
+    pub fn hall_of_fame(&self) -> JsValue {
+        swb::to_value(self.sim.hall_of_fame().members()).unwrap()
+    }
+
+    /// Replaces up to `count` birds that ate the least with the best ones
+    /// from the hall of fame; returns how many got replaced.
+    #[wasm_bindgen(js_name = reinjectHallOfFame)]
+    pub fn reinject_hall_of_fame(&mut self, count: usize) -> Result<usize, JsValue> {
+        self.sim.reinject_hall_of_fame(count).map_err(to_js_error)
     }
 }
 
+impl Simulation {
+    fn from_config(config: sim::Config) -> Result<Self, JsValue> {
+        let sim = sim::Simulation::random(config, &mut thread_rng()).map_err(to_js_error)?;
+
+        Ok(Self { sim })
+    }
+}
+
+fn to_js_error(err: impl std::fmt::Display) -> JsValue {
+    JsValue::from_str(&err.to_string())
+}
+
 #[derive(Clone, Debug, Serialize)]
 pub struct World {
     pub animals: Vec<Animal>,
     pub foods: Vec<Food>,
+    pub obstacles: Vec<Obstacle>,
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -43,11 +133,20 @@ pub struct Food {
     pub y: f32,
 }
 
+#[derive(Clone, Debug, Serialize)]
+pub struct Obstacle {
+    pub x: f32,
+    pub y: f32,
+    pub radius: f32,
+}
+
 #[derive(Clone, Debug, Serialize)]
 pub struct Animal {
     pub x: f32,
     pub y: f32,
     pub rotation: f32,
+    pub energy: f32,
+    pub alive: bool,
 }
 
 impl From<&sim::World> for World {
@@ -64,7 +163,17 @@ impl From<&sim::World> for World {
             .map(Food::from)
             .collect();
 
-        Self { animals, foods }
+        let obstacles = world
+            .obstacles()
+            .iter()
+            .map(Obstacle::from)
+            .collect();
+
+        Self {
+            animals,
+            foods,
+            obstacles,
+        }
     }
 }
 
@@ -74,6 +183,8 @@ impl From<&sim::Animal> for Animal {
             x: animal.position().x,
             y: animal.position().y,
             rotation: animal.rotation().angle(),
+            energy: animal.energy(),
+            alive: animal.is_alive(),
         }
     }
 }
@@ -86,3 +197,13 @@ impl From<&sim::Food> for Food {
         }
     }
 }
+
+impl From<&sim::Obstacle> for Obstacle {
+    fn from(obstacle: &sim::Obstacle) -> Self {
+        Self {
+            x: obstacle.center().x,
+            y: obstacle.center().y,
+            radius: obstacle.radius(),
+        }
+    }
+}
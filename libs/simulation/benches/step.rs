@@ -0,0 +1,48 @@
+//! Measures how a single step scales with the size of the world.
+//!
+//! Run with `cargo bench -p lib-simulation`.
+//!
+//! Our world is always a unit square, so to simulate a bigger world (as
+//! opposed to a more crowded one) everything spatial - ranges, sizes and
+//! speeds - gets scaled down as birds and foods are added; this way each
+//! bird sees, on average, as many foods as it does with the default config.
+//!
+//! Thanks to `Grid`, time of a step should grow roughly linearly with the
+//! size - comparing every bird with every food made it grow quadratically.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use lib_simulation::{Config, Simulation};
+
+fn config(size: usize) -> Config {
+    let default = Config::default();
+    let scale = (default.world_foods as f32 / size as f32).sqrt();
+
+    Config {
+        eye_fov_range: default.eye_fov_range * scale,
+        sim_speed_min: default.sim_speed_min * scale,
+        sim_speed_max: default.sim_speed_max * scale,
+        food_size: default.food_size * scale,
+        world_animals: size,
+        world_foods: size,
+        // Long enough not to evolve in the middle of measuring
+        sim_generation_length: usize::MAX,
+        ..default
+    }
+}
+
+fn step(c: &mut Criterion) {
+    let mut group = c.benchmark_group("step");
+
+    for size in [100, 1000, 4000] {
+        let mut simulation = Simulation::from_seed(0, config(size)).unwrap();
+
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, _| {
+            b.iter(|| simulation.step().unwrap());
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, step);
+criterion_main!(benches);
@@ -1,10 +1,11 @@
 use lib_neural_network as nn;
 use nalgebra as na;
 use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
 
-use crate::Eye;
+use crate::{ga, obstacle, Config, Error, Eye};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Animal {
     pub(crate) position: na::Point2<f32>,
     pub(crate) rotation: na::Rotation2<f32>,
@@ -12,61 +13,140 @@ pub struct Animal {
     pub(crate) eye: Eye,
     pub(crate) brain: nn::Network,
     pub(crate) satiation: usize,
+    /// What's left of the bird's energy; once it hits zero, the bird is
+    /// dead for the rest of the generation.
+    pub(crate) energy: f32,
+    /// How much energy the bird has burnt during current generation.
+    pub(crate) energy_spent: f32,
+    /// How far the bird has flown during current generation.
+    pub(crate) distance: f32,
+    /// Sum of all positions the bird has visited during current
+    /// generation; divided by the number of steps, it gives the bird's
+    /// average position.
+    pub(crate) position_sum: na::Vector2<f32>,
 }
 
 impl Animal {
-    pub fn random(rng: &mut dyn RngCore) -> Self {
-        let eye = Eye::default();
-
-        let brain = nn::Network::random(
-            rng,
-            &[
-                // The Input Layer
-                //
-                // Because our eye returns Vec<f32>, and our neural
-                // network works on Vec<f32>, we can pass-through
-                // numbers from eye into the neural network directly.
-                //
-                // Had our birdies had, I dunno, ears, we could do
-                // something like: `eye.cells() + ear.nerves()` etc.
-                nn::LayerTopology {
-                    neurons: eye.cells(),
-                },
-                // The Hidden Layer
-                //
-                // There is no best answer as to "how many neurons
-                // the hidden layer should contain" (or how many
-                // hidden layers there should be, even - there could
-                // be zero, one, two or more!).
-                //
-                // The rule of thumb is to start with a single hidden
-                // layer that has somewhat more neurons that the input
-                // layer, and see how well the network performs.
-                nn::LayerTopology {
-                    neurons: 2 * eye.cells(),
-                },
-                // The Output Layer
-                //
-                // Since the brain will control our bird's speed and
-                // rotation, this gives us two numbers = two neurons.
-                nn::LayerTopology { neurons: 2 },
-            ],
-        );
+    pub fn random(config: &Config, rng: &mut dyn RngCore) -> Result<Self, Error> {
+        let eye = Eye::from_config(config)?;
+        let brain = nn::Network::random(rng, &Self::topology(config, &eye));
 
+        Ok(Self::new(config, eye, brain, rng))
+    }
+
+    /// Rebuilds an animal from a chromosome produced by the genetic
+    /// algorithm - the chromosome holds the brain's weights, laid out the
+    /// same way `nn::Network::weights()` returns them.
+    pub(crate) fn from_chromosome(
+        config: &Config,
+        chromosome: ga::Chromosome,
+        rng: &mut dyn RngCore,
+    ) -> Result<Self, Error> {
+        let eye = Eye::from_config(config)?;
+        let brain = nn::Network::from_weights(&Self::topology(config, &eye), chromosome)?;
+
+        Ok(Self::new(config, eye, brain, rng))
+    }
+
+    /// Creates an animal with an already existing brain, e.g. one trained
+    /// offline.
+    pub(crate) fn from_brain(
+        config: &Config,
+        brain: nn::Network,
+        rng: &mut dyn RngCore,
+    ) -> Result<Self, Error> {
+        let eye = Eye::from_config(config)?;
+
+        Self::check_brain(config, &eye, &brain)?;
+
+        Ok(Self::new(config, eye, brain, rng))
+    }
+
+    /// Checks whether the animal (e.g. one loaded from a snapshot) has the
+    /// eye and brain given config would give it.
+    pub(crate) fn check(&self, config: &Config) -> Result<(), Error> {
+        let eye = Eye::from_config(config)?;
+
+        if self.eye != eye {
+            return Err(Error::SnapshotMismatch("eye"));
+        }
+
+        Self::check_brain(config, &eye, &self.brain)
+    }
+
+    fn check_brain(config: &Config, eye: &Eye, brain: &nn::Network) -> Result<(), Error> {
+        let expected = Self::topology(config, eye).to_vec();
+        let actual = brain.topology();
+
+        if expected != actual {
+            return Err(Error::BrainMismatch { expected, actual });
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn as_chromosome(&self) -> ga::Chromosome {
+        // We evolve only our birds' brains, but technically there's no
+        // reason not to simulate e.g. physical properties such as size.
+        //
+        // If that was to happen, this function could be adjusted to
+        // return a longer chromosome that encodes not only the brain,
+        // but also, say, birdie's color.
+        self.brain.weights().into_iter().collect()
+    }
+
+    fn new(config: &Config, eye: Eye, brain: nn::Network, rng: &mut dyn RngCore) -> Self {
         Self {
-            position: rng.gen(),
-            // ------ ^-------^
-            // | If not for `rand-no-std`, we'd have to do awkward
-            // | `na::Point2::new(rng.gen(), rng.gen())` instead
-            // ---
+            // Spawning inside of an obstacle would leave the bird stuck
+            // there for the entire generation
+            position: obstacle::free_position(rng, config.world_boundary, &config.world_obstacles),
             rotation: rng.gen(),
             speed: 0.002,
             eye,
             brain,
             satiation: 0,
+            energy: config.energy_initial,
+            energy_spent: 0.0,
+            distance: 0.0,
+            position_sum: na::Vector2::zeros(),
         }
     }
 
+    fn topology(config: &Config, eye: &Eye) -> [nn::LayerTopology; 3] {
+        [
+            // The Input Layer
+            //
+            // Because our eye returns Vec<f32>, and our neural
+            // network works on Vec<f32>, we can pass-through
+            // numbers from eye into the neural network directly.
+            //
+            // Had our birdies had, I dunno, ears, we could do
+            // something like: `eye.inputs() + ear.nerves()` etc.
+            nn::LayerTopology::new(eye.inputs()),
+            // The Hidden Layer
+            //
+            // There is no best answer as to "how many neurons
+            // the hidden layer should contain" (or how many
+            // hidden layers there should be, even - there could
+            // be zero, one, two or more!).
+            //
+            // The rule of thumb is to start with a single hidden
+            // layer that has somewhat more neurons that the input
+            // layer, and see how well the network performs.
+            nn::LayerTopology::new(2 * eye.inputs())
+                .with_activation(config.brain_hidden_activation),
+            // The Output Layer
+            //
+            // Since the brain will control our bird's speed and
+            // rotation, this gives us two numbers = two neurons.
+            //
+            // Both numbers are relative (see `process_brains()`), so the
+            // activation here should be able to go negative - otherwise
+            // birds could only ever speed up and turn one way.
+            nn::LayerTopology::new(2).with_activation(config.brain_output_activation),
+        ]
+    }
+
     pub fn position(&self) -> na::Point2<f32> {
         // ------------------ ^
         // | No need to return a reference, because na::Point2 is Copy.
@@ -86,4 +166,50 @@ impl Animal {
     pub fn rotation(&self) -> na::Rotation2<f32> {
         self.rotation
     }
+
+    pub fn satiation(&self) -> usize {
+        self.satiation
+    }
+
+    pub fn energy(&self) -> f32 {
+        self.energy
+    }
+
+    /// Whether the bird still has some energy left - dead birds neither
+    /// think, nor move, nor eat.
+    pub fn is_alive(&self) -> bool {
+        self.energy > 0.0
+    }
+
+    /// Burns energy the bird has used during a step, during which it's
+    /// flown at given speed and turned by given angle.
+    pub(crate) fn metabolize(&mut self, config: &Config, rotation: f32) {
+        let cost = config.energy_metabolism
+            + config.energy_speed_cost * self.speed
+            + config.energy_rotation_cost * rotation.abs();
+
+        self.energy_spent += cost.min(self.energy);
+        self.energy = (self.energy - cost).max(0.0);
+    }
+
+    pub fn energy_spent(&self) -> f32 {
+        self.energy_spent
+    }
+
+    pub fn distance(&self) -> f32 {
+        self.distance
+    }
+
+    /// Describes how the bird behaved during `steps` steps of current
+    /// generation - where it ended up and where it hung around on average;
+    /// used by novelty search.
+    pub(crate) fn behaviour(&self, steps: usize) -> Vec<f32> {
+        let average = self.position_sum / steps.max(1) as f32;
+
+        vec![self.position.x, self.position.y, average.x, average.y]
+    }
+
+    pub fn brain(&self) -> &nn::Network {
+        &self.brain
+    }
 }
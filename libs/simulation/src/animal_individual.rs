@@ -1,26 +1,75 @@
 use crate::*;
 
+#[derive(Clone)]
 pub struct AnimalIndividual {
     fitness: f32,
+    satiation: f32,
+    distance: f32,
+    energy_spent: f32,
     chromosome: ga::Chromosome,
 }
 
 impl AnimalIndividual {
+    #[cfg(test)]
+    pub(crate) fn new(fitness: f32, chromosome: ga::Chromosome) -> Self {
+        Self {
+            fitness,
+            satiation: fitness,
+            distance: 0.0,
+            energy_spent: 0.0,
+            chromosome,
+        }
+    }
+
     pub fn from_animal(animal: &Animal) -> Self {
         Self {
-            chromosome: animal.brain.weights(),
-            fitness: animal.satiation as f32,
+            chromosome: animal.as_chromosome(),
+            fitness: Self::foraging(animal.satiation as f32, animal.energy_spent),
+            satiation: animal.satiation as f32,
+            distance: animal.distance,
+            energy_spent: animal.energy_spent,
         }
     }
 
-    pub fn into_animal(&self, rng: &mut dyn RngCore) -> Animal {
-        todo!();
+    /// How well the bird foraged: how much it ate, divided by `1.0 +` how
+    /// much energy it spent on that - so out of two birds that ate the
+    /// same, the one that spent less is the better one.
+    ///
+    /// Without the energy model (see `Config::energy_metabolism` & co.)
+    /// nobody spends anything and this is just the satiation.
+    fn foraging(satiation: f32, energy_spent: f32) -> f32 {
+        satiation / (1.0 + energy_spent)
+    }
+
+    /// How much the bird ate - unlike fitness, this is affected by neither
+    /// energy nor novelty.
+    pub(crate) fn satiation(&self) -> f32 {
+        self.satiation
+    }
+
+    /// Mixes bird's novelty into its fitness:
+    /// - 0.0 = fitness is just the foraging (see [`Self::foraging()`]),
+    /// - 1.0 = fitness is just the novelty.
+    pub(crate) fn blend_novelty(&mut self, novelty: f32, weight: f32) {
+        let foraging = Self::foraging(self.satiation, self.energy_spent);
+
+        self.fitness = (1.0 - weight) * foraging + weight * novelty;
+    }
+
+    pub fn into_animal(self, config: &Config, rng: &mut dyn RngCore) -> Result<Animal, Error> {
+        Animal::from_chromosome(config, self.chromosome, rng)
     }
 }
 
 impl ga::Individual for AnimalIndividual {
     fn create(chromosome: ga::Chromosome) -> Self {
-        todo!()
+        Self {
+            fitness: 0.0,
+            satiation: 0.0,
+            distance: 0.0,
+            energy_spent: 0.0,
+            chromosome,
+        }
     }
 
     fn fitness(&self) -> f32 {
@@ -31,3 +80,87 @@ impl ga::Individual for AnimalIndividual {
         &self.chromosome
     }
 }
+
+/// Birds want to eat as much as possible, while flying as little and
+/// spending as little energy as possible.
+///
+/// Food goes by satiation, not fitness - fitness might have novelty mixed
+/// into it already.
+impl ga::MultiObjectiveIndividual for AnimalIndividual {
+    fn objectives(&self) -> Vec<f32> {
+        vec![self.satiation, -self.distance, -self.energy_spent]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn round_trip() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let config = Config::default();
+        let animal = Animal::random(&config, &mut rng).unwrap();
+
+        let individual = AnimalIndividual::from_animal(&animal);
+        let restored = individual.into_animal(&config, &mut rng).unwrap();
+
+        assert_eq!(animal.brain.weights(), restored.brain.weights());
+    }
+
+    #[test]
+    fn create() {
+        let chromosome: ga::Chromosome = vec![1.0, 2.0, 3.0].into_iter().collect();
+        let individual = <AnimalIndividual as ga::Individual>::create(chromosome);
+
+        assert_eq!(ga::Individual::fitness(&individual), 0.0);
+        assert_eq!(ga::Individual::chromosome(&individual).len(), 3);
+    }
+
+    #[test]
+    fn blend_novelty() {
+        let mut individual = AnimalIndividual::new(4.0, std::iter::empty().collect());
+
+        individual.blend_novelty(2.0, 0.25);
+
+        assert_eq!(ga::Individual::fitness(&individual), 3.5);
+        assert_eq!(individual.satiation(), 4.0);
+    }
+
+    #[test]
+    fn spending_less_energy_scores_higher() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut animal = Animal::random(&Config::default(), &mut rng).unwrap();
+
+        animal.satiation = 3;
+        animal.energy_spent = 0.5;
+        let frugal = AnimalIndividual::from_animal(&animal);
+
+        animal.energy_spent = 2.0;
+        let wasteful = AnimalIndividual::from_animal(&animal);
+
+        assert_eq!(ga::Individual::fitness(&frugal), 2.0);
+        assert_eq!(ga::Individual::fitness(&wasteful), 1.0);
+        assert_eq!(frugal.satiation(), wasteful.satiation());
+    }
+
+    #[test]
+    fn objectives() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut animal = Animal::random(&Config::default(), &mut rng).unwrap();
+
+        animal.satiation = 3;
+        animal.distance = 0.5;
+        animal.energy_spent = 0.25;
+
+        let mut individual = AnimalIndividual::from_animal(&animal);
+        individual.blend_novelty(10.0, 0.5);
+
+        assert_eq!(
+            ga::MultiObjectiveIndividual::objectives(&individual),
+            [3.0, -0.5, -0.25]
+        );
+    }
+}
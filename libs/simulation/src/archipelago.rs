@@ -0,0 +1,209 @@
+use rand::RngCore;
+use rand_chacha::ChaCha8Rng;
+
+use crate::*;
+
+/// Island mode: several simulations, each with its own world, whose birds
+/// evolve separately and only every now and then migrate between islands -
+/// see `ga::IslandModel`.
+///
+/// Number of islands and migration settings come from the `ga_islands`
+/// and `ga_migration_*` fields of [`Config`].
+pub struct Archipelago {
+    islands: Vec<Simulation>,
+    model: ga::IslandModel<Selection>,
+    rng: ChaCha8Rng,
+    generation: usize,
+}
+
+impl Archipelago {
+    /// Creates an archipelago seeded from given generator; see
+    /// [`Simulation::random()`].
+    pub fn random(config: Config, rng: &mut dyn RngCore) -> Result<Self, Error> {
+        Self::from_seed(rng.next_u64(), config)
+    }
+
+    pub fn from_seed(seed: u64, config: Config) -> Result<Self, Error> {
+        config.validate()?;
+
+        let mut rng = Streams::archipelago(seed);
+
+        let islands = (0..config.ga_islands)
+            .map(|_| Simulation::from_seed(rng.next_u64(), config.clone()))
+            .collect::<Result<_, _>>()?;
+
+        let model = ga::IslandModel::new(
+            (0..config.ga_islands)
+                .map(|_| Simulation::genetic_algorithm(&config))
+                .collect::<Result<_, _>>()?,
+            ga::Migration {
+                topology: config.ga_migration_topology,
+                interval: config.ga_migration_interval,
+                migrants: config.ga_migrants,
+            },
+        )?;
+
+        Ok(Self {
+            islands,
+            model,
+            rng,
+            generation: 0,
+        })
+    }
+
+    pub fn islands(&self) -> &[Simulation] {
+        &self.islands
+    }
+
+    /// Index of the generation that's currently being simulated.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Performs a single step on each island; when it ends the current
+    /// generation, returns statistics of that generation, one per island.
+    ///
+    /// Migrants get exchanged between whole generations, so islands evolve
+    /// all at once - an island that's finished its generation early (e.g.
+    /// because all of its birds have starved) waits for the others.
+    pub fn step(&mut self) -> Result<Option<Vec<GenerationStatistics>>, Error> {
+        for island in &mut self.islands {
+            if !island.generation_finished() {
+                island.advance()?;
+            }
+        }
+
+        if !self.islands.iter().all(Simulation::generation_finished) {
+            return Ok(None);
+        }
+
+        let current: Vec<_> = self
+            .islands
+            .iter_mut()
+            .map(Simulation::individuals)
+            .collect();
+
+        let evolved = self
+            .model
+            .evolve_at(&mut self.rng, &current, self.generation)?;
+
+        let statistics = self
+            .islands
+            .iter_mut()
+            .zip(current.iter().zip(evolved))
+            .map(|(island, (current, evolved))| island.repopulate(current, evolved))
+            .collect::<Result<_, _>>()?;
+
+        self.generation += 1;
+
+        Ok(Some(statistics))
+    }
+
+    /// Fast-forwards until the end of the current generation.
+    pub fn train(&mut self) -> Result<Vec<GenerationStatistics>, Error> {
+        loop {
+            if let Some(statistics) = self.step()? {
+                return Ok(statistics);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> Config {
+        Config {
+            sim_generation_length: 50,
+            world_animals: 6,
+            world_foods: 30,
+            ga_islands: 3,
+            ga_migration_interval: 2,
+            ga_migrants: 1,
+            ..Default::default()
+        }
+    }
+
+    fn weights(archipelago: &Archipelago) -> Vec<Vec<f32>> {
+        archipelago
+            .islands()
+            .iter()
+            .map(|island| {
+                island
+                    .world()
+                    .animals()
+                    .iter()
+                    .flat_map(|animal| animal.brain().weights())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn trains_every_island() {
+        let mut archipelago = Archipelago::from_seed(7, config()).unwrap();
+
+        for generation in 0..3 {
+            let statistics = archipelago.train().unwrap();
+
+            assert_eq!(statistics.len(), 3);
+            assert!(statistics.iter().all(|s| s.generation == generation));
+        }
+
+        assert_eq!(archipelago.generation(), 3);
+        assert!(archipelago.islands().iter().all(|i| i.generation() == 3));
+    }
+
+    #[test]
+    fn waits_for_every_island() {
+        let mut archipelago = Archipelago::from_seed(7, config()).unwrap();
+
+        for animal in &mut archipelago.islands[0].world.animals {
+            animal.energy = 0.0;
+        }
+
+        assert_eq!(archipelago.step().unwrap(), None);
+        assert_eq!(archipelago.islands[0].age, 0);
+        assert_eq!(archipelago.islands[1].age, 1);
+
+        let statistics = archipelago.train().unwrap();
+
+        assert_eq!(statistics[0].food_eaten, 0);
+        assert_eq!(archipelago.generation(), 1);
+    }
+
+    #[test]
+    fn islands_are_separate_worlds() {
+        let archipelago = Archipelago::from_seed(7, config()).unwrap();
+        let weights = weights(&archipelago);
+
+        assert_ne!(weights[0], weights[1]);
+        assert_ne!(weights[1], weights[2]);
+    }
+
+    #[test]
+    fn same_seed_gives_same_archipelago() {
+        let mut a = Archipelago::from_seed(7, config()).unwrap();
+        let mut b = Archipelago::from_seed(7, config()).unwrap();
+
+        for _ in 0..3 {
+            assert_eq!(a.train().unwrap(), b.train().unwrap());
+        }
+
+        assert_eq!(weights(&a), weights(&b));
+    }
+
+    #[test]
+    fn no_islands() {
+        let config = Config {
+            ga_islands: 0,
+            ..config()
+        };
+
+        assert!(matches!(
+            Archipelago::from_seed(7, config),
+            Err(Error::GeneticAlgorithm(ga::Error::NoIslands))
+        ));
+    }
+}
@@ -0,0 +1,125 @@
+use std::f32::consts::PI;
+
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// What happens at the edges of our (unit square) world.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Boundary {
+    /// Edges wrap around - flying off the right edge brings the bird back
+    /// on the left one; birds also see and eat across the edges.
+    #[default]
+    Toroidal,
+
+    /// Edges are walls that birds can't fly (nor see) through.
+    Walled,
+}
+
+impl Boundary {
+    /// Returns the vector that leads from `from` to `to`; on a torus, that's
+    /// the shortest of all such vectors (so called minimum image).
+    pub fn displacement(self, from: na::Point2<f32>, to: na::Point2<f32>) -> na::Vector2<f32> {
+        let vec = to - from;
+
+        match self {
+            Self::Toroidal => vec.map(|d| d - d.round()),
+            Self::Walled => vec,
+        }
+    }
+
+    pub fn distance(self, from: na::Point2<f32>, to: na::Point2<f32>) -> f32 {
+        self.displacement(from, to).norm()
+    }
+
+    /// Brings a position that has left the world back into it.
+    pub(crate) fn confine(self, position: na::Point2<f32>) -> na::Point2<f32> {
+        match self {
+            Self::Toroidal => position.map(|p| na::wrap(p, 0.0, 1.0)),
+            Self::Walled => position.map(|p| p.clamp(0.0, 1.0)),
+        }
+    }
+}
+
+/// What happens to a bird that flies into a wall.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WallCollision {
+    /// Bird stops at the wall, sliding along it for as long as it keeps
+    /// flying into it.
+    Stop,
+
+    /// Bird bounces off the wall, like a billiard ball.
+    #[default]
+    Bounce,
+}
+
+impl WallCollision {
+    /// Handles a bird that might have just flown past a wall, keeping it
+    /// inside of the world.
+    pub(crate) fn collide(self, position: &mut na::Point2<f32>, rotation: &mut na::Rotation2<f32>) {
+        if self == Self::Bounce {
+            let mut angle = rotation.angle();
+
+            if position.x < 0.0 || position.x > 1.0 {
+                position.x = reflect(position.x);
+                angle = PI - angle;
+            }
+
+            if position.y < 0.0 || position.y > 1.0 {
+                position.y = reflect(position.y);
+                angle = -angle;
+            }
+
+            *rotation = na::Rotation2::new(angle);
+        }
+
+        *position = Boundary::Walled.confine(*position);
+    }
+}
+
+/// Mirrors a coordinate that's gone past a wall back inside of the world.
+fn reflect(coord: f32) -> f32 {
+    if coord < 0.0 {
+        -coord
+    } else {
+        2.0 - coord
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(Boundary::Toroidal, (0.9, 0.5), (0.1, 0.5), (0.2, 0.0))]
+    #[test_case(Boundary::Toroidal, (0.1, 0.5), (0.9, 0.5), (-0.2, 0.0))]
+    #[test_case(Boundary::Toroidal, (0.5, 0.05), (0.5, 0.95), (0.0, -0.1))]
+    #[test_case(Boundary::Toroidal, (0.4, 0.4), (0.6, 0.7), (0.2, 0.3))]
+    #[test_case(Boundary::Walled, (0.9, 0.5), (0.1, 0.5), (-0.8, 0.0))]
+    #[test_case(Boundary::Walled, (0.4, 0.4), (0.6, 0.7), (0.2, 0.3))]
+    fn displacement(boundary: Boundary, from: (f32, f32), to: (f32, f32), expected: (f32, f32)) {
+        let actual =
+            boundary.displacement(na::Point2::new(from.0, from.1), na::Point2::new(to.0, to.1));
+
+        approx::assert_relative_eq!(
+            actual,
+            na::Vector2::new(expected.0, expected.1),
+            epsilon = 1e-6
+        );
+    }
+
+    #[test_case(Boundary::Toroidal, (1.1, -0.2), (0.1, 0.8))]
+    #[test_case(Boundary::Walled, (1.1, -0.2), (1.0, 0.0))]
+    #[test_case(Boundary::Walled, (0.3, 0.7), (0.3, 0.7))]
+    fn confine(boundary: Boundary, position: (f32, f32), expected: (f32, f32)) {
+        let actual = boundary.confine(na::Point2::new(position.0, position.1));
+
+        approx::assert_relative_eq!(
+            actual,
+            na::Point2::new(expected.0, expected.1),
+            epsilon = 1e-6
+        );
+    }
+}
@@ -0,0 +1,471 @@
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ga, nn, Boundary, Error, Eye, Obstacle, Selection, WallCollision};
+
+/// Everything that shapes an experiment.
+///
+/// Missing fields fall back to their defaults, so a config file only has
+/// to mention the parameters it wants to change.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Activation of the brain's hidden layer.
+    pub brain_hidden_activation: nn::Activation,
+
+    /// Activation of the brain's output layer; must be symmetric (see
+    /// `nn::Activation::is_symmetric()`), otherwise birds can only turn
+    /// one way.
+    pub brain_output_activation: nn::Activation,
+
+    /// How far our eye can see, as a fraction of the world's size - e.g.
+    /// the default of 0.25 means a quarter of the map.
+    pub eye_fov_range: f32,
+
+    /// How wide our eye can see, in radians.
+    pub eye_fov_angle: f32,
+
+    /// How many photoreceptors there are in a single eye; also the size
+    /// of the brain's input layer (twice that in a walled world, where
+    /// eyes see walls, too).
+    pub eye_cells: usize,
+
+    /// How parents of the next generation get picked.
+    pub ga_selection: Selection,
+
+    /// Chance of a gene being mutated, see `ga::UniformMutation`.
+    pub ga_mut_chance: f32,
+
+    /// Magnitude of a single mutation, see `ga::UniformMutation`.
+    pub ga_mut_coeff: f32,
+
+    /// How many islands (separate worlds) there are, see `Archipelago`;
+    /// a plain `Simulation` ignores this and the other island settings.
+    pub ga_islands: usize,
+
+    /// Islands exchange birds after every this many generations.
+    pub ga_migration_interval: usize,
+
+    /// How many birds each island receives during migration.
+    pub ga_migrants: usize,
+
+    /// Which islands send birds where.
+    pub ga_migration_topology: ga::Topology,
+
+    /// Whether to evolve birds with NSGA-II (see `ga::Nsga2`), trading off
+    /// food eaten against distance flown and energy spent, instead of going
+    /// just by the food.
+    ///
+    /// Energy counts only when the energy model is on (see
+    /// `energy_metabolism` & co.) - otherwise everybody spends none of it.
+    ///
+    /// Only a plain `Simulation` supports this; islands of an
+    /// `Archipelago` always evolve by food alone.
+    pub ga_multi_objective: bool,
+
+    /// How much novelty (see `ga::NoveltyArchive`) counts into bird's
+    /// fitness, instead of food (per energy spent, see
+    /// `energy_rotation_cost`):
+    /// - 0.0 = just food,
+    /// - 1.0 = just novelty.
+    ///
+    /// Novelty is the distance between bird behaviours (where it flew),
+    /// so it's usually much smaller than the number of foods eaten.
+    pub ga_novelty_weight: f32,
+
+    /// How many nearest behaviours novelty gets compared against.
+    pub ga_novelty_neighbours: usize,
+
+    /// How many behaviours the novelty archive remembers.
+    pub ga_novelty_archive_size: usize,
+
+    /// How many of the most novel behaviours get archived after each
+    /// generation.
+    pub ga_novelty_archived: usize,
+
+    /// How many of the best birds ever seen to remember, see
+    /// `HallOfFame`.
+    pub ga_hall_of_fame_size: usize,
+
+    /// Minimum speed of a bird.
+    pub sim_speed_min: f32,
+
+    /// Maximum speed of a bird.
+    pub sim_speed_max: f32,
+
+    /// How much the brain can change the speed in a single step.
+    pub sim_speed_accel: f32,
+
+    /// How much the brain can change the rotation in a single step.
+    pub sim_rotation_accel: f32,
+
+    /// How many steps each generation lasts.
+    pub sim_generation_length: usize,
+
+    /// Energy each bird starts a generation with.
+    pub energy_initial: f32,
+
+    /// Energy a bird gains by eating a single food.
+    pub energy_per_food: f32,
+
+    /// Energy a bird burns each step, just for being alive.
+    pub energy_metabolism: f32,
+
+    /// Energy a bird burns each step per unit of its speed.
+    pub energy_speed_cost: f32,
+
+    /// Energy a bird burns per radian it turns.
+    ///
+    /// With all of the costs at zero (as they are by default) birds never
+    /// run out of energy; otherwise birds that squander it die early and
+    /// so eat less - and since fitness is the food eaten divided by
+    /// `1.0 +` the energy spent, out of birds that ate the same the ones
+    /// that spent less get ahead, making the evolution favour efficient
+    /// foraging.
+    pub energy_rotation_cost: f32,
+
+    /// Distance at which a bird eats food.
+    pub food_size: f32,
+
+    /// How many birds there are in the world.
+    pub world_animals: usize,
+
+    /// How many foods there are in the world.
+    pub world_foods: usize,
+
+    /// Whether the world wraps around or is surrounded by walls.
+    pub world_boundary: Boundary,
+
+    /// What happens to birds that fly into a wall; used only when the
+    /// world is walled.
+    pub world_wall_collision: WallCollision,
+
+    /// Things birds can neither fly nor see through.
+    pub world_obstacles: Vec<Obstacle>,
+}
+
+impl Config {
+    /// Checks whether a simulation can be built from this config - handy
+    /// when the config comes from a file or the browser.
+    pub fn validate(&self) -> Result<(), Error> {
+        if !self.brain_output_activation.is_symmetric() {
+            return Err(Error::AsymmetricOutputActivation(
+                self.brain_output_activation,
+            ));
+        }
+
+        Eye::from_config(self)?;
+
+        let (min, max) = (self.sim_speed_min, self.sim_speed_max);
+
+        if min.is_nan() || max.is_nan() || min <= 0.0 || min > max {
+            return Err(Error::InvalidSpeedRange { min, max });
+        }
+
+        if self.sim_speed_accel.is_nan() || self.sim_speed_accel < 0.0 {
+            return Err(Error::InvalidSpeedAccel(self.sim_speed_accel));
+        }
+
+        if self.sim_rotation_accel.is_nan() || self.sim_rotation_accel < 0.0 {
+            return Err(Error::InvalidRotationAccel(self.sim_rotation_accel));
+        }
+
+        if self.energy_initial.is_nan() || self.energy_initial <= 0.0 {
+            return Err(Error::InvalidInitialEnergy(self.energy_initial));
+        }
+
+        for (name, value) in [
+            ("energy per food", self.energy_per_food),
+            ("metabolism", self.energy_metabolism),
+            ("speed cost", self.energy_speed_cost),
+            ("rotation cost", self.energy_rotation_cost),
+        ] {
+            if value.is_nan() || value < 0.0 {
+                return Err(Error::NegativeEnergy { name, value });
+            }
+        }
+
+        if self.food_size.is_nan() || self.food_size < 0.0 {
+            return Err(Error::InvalidFoodSize(self.food_size));
+        }
+
+        if self.world_animals == 0 {
+            return Err(Error::NoAnimals);
+        }
+
+        for obstacle in &self.world_obstacles {
+            Obstacle::new(obstacle.center, obstacle.radius)?;
+        }
+
+        self.ga_selection.validate()?;
+        ga::UniformMutation::new(self.ga_mut_chance, self.ga_mut_coeff)?;
+
+        if self.ga_islands == 0 {
+            return Err(ga::Error::NoIslands.into());
+        }
+
+        if self.ga_migration_interval == 0 {
+            return Err(ga::Error::InvalidMigrationInterval(self.ga_migration_interval).into());
+        }
+
+        if self.ga_migrants > self.world_animals {
+            return Err(ga::Error::PopulationTooSmall {
+                required: self.ga_migrants,
+                actual: self.world_animals,
+            }
+            .into());
+        }
+
+        if !(0.0..=1.0).contains(&self.ga_novelty_weight) {
+            return Err(Error::InvalidNoveltyWeight(self.ga_novelty_weight));
+        }
+
+        self.novelty_archive()?;
+
+        if self.ga_multi_objective && self.world_animals < 2 {
+            return Err(ga::Error::PopulationTooSmall {
+                required: 2,
+                actual: self.world_animals,
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+}
+
+impl Config {
+    pub(crate) fn novelty_archive(&self) -> Result<ga::NoveltyArchive, Error> {
+        Ok(ga::NoveltyArchive::new(
+            self.ga_novelty_neighbours,
+            self.ga_novelty_archive_size,
+            self.ga_novelty_archived,
+        )?)
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            brain_hidden_activation: nn::Activation::ReLU,
+            brain_output_activation: nn::Activation::Tanh,
+
+            eye_fov_range: 0.25,
+            eye_fov_angle: PI + FRAC_PI_4,
+            eye_cells: 9,
+
+            ga_selection: Selection::RouletteWheel,
+            ga_mut_chance: 0.01,
+            ga_mut_coeff: 0.3,
+            // ----------- ^-^
+            // | Chosen with a bit of experimentation.
+            // |
+            // | Higher values can make the simulation more chaotic,
+            // | which - a bit counterintuitively - might allow for
+            // | it to discover *better* solutions; but the trade-off
+            // | is that higher values might also cause current, good
+            // | enough solutions to be discarded.
+            // ---
+            ga_islands: 4,
+            ga_migration_interval: 5,
+            ga_migrants: 2,
+            ga_migration_topology: ga::Topology::Ring,
+            ga_multi_objective: false,
+            ga_novelty_weight: 0.0,
+            ga_novelty_neighbours: 15,
+            ga_novelty_archive_size: 250,
+            ga_novelty_archived: 2,
+            ga_hall_of_fame_size: 10,
+
+            sim_speed_min: 0.001,
+            sim_speed_max: 0.005,
+            sim_speed_accel: 0.2,
+            sim_rotation_accel: FRAC_PI_2,
+            sim_generation_length: 2500,
+
+            energy_initial: 1.0,
+            energy_per_food: 0.5,
+            energy_metabolism: 0.0,
+            energy_speed_cost: 0.0,
+            energy_rotation_cost: 0.0,
+
+            food_size: 0.01,
+
+            world_animals: 40,
+            world_foods: 60,
+            world_boundary: Boundary::Toroidal,
+            world_wall_collision: WallCollision::Bounce,
+            world_obstacles: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra as na;
+    use test_case::test_case;
+
+    #[test]
+    fn partial_config_falls_back_to_defaults() {
+        let config: Config =
+            serde_json::from_str(r#"{ "world_animals": 100, "ga_mut_chance": 0.05 }"#).unwrap();
+
+        assert_eq!(
+            config,
+            Config {
+                world_animals: 100,
+                ga_mut_chance: 0.05,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let config = Config::default();
+        let json = serde_json::to_string(&config).unwrap();
+
+        assert_eq!(serde_json::from_str::<Config>(&json).unwrap(), config);
+    }
+
+    #[test]
+    fn default_is_valid() {
+        assert!(Config::default().validate().is_ok());
+    }
+
+    #[test_case(nn::Activation::ReLU)]
+    #[test_case(nn::Activation::LeakyReLU)]
+    #[test_case(nn::Activation::Sigmoid)]
+    fn asymmetric_output_activation(activation: nn::Activation) {
+        let config = Config {
+            brain_output_activation: activation,
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            config.validate(),
+            Err(Error::AsymmetricOutputActivation(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_speed_range() {
+        let config = Config {
+            sim_speed_min: 0.5,
+            sim_speed_max: 0.1,
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            config.validate(),
+            Err(Error::InvalidSpeedRange { .. })
+        ));
+    }
+
+    #[test]
+    fn invalid_mutation_chance() {
+        let config = Config {
+            ga_mut_chance: 2.0,
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            config.validate(),
+            Err(Error::GeneticAlgorithm(ga::Error::InvalidMutationChance(_)))
+        ));
+    }
+
+    #[test]
+    fn invalid_novelty_weight() {
+        let config = Config {
+            ga_novelty_weight: 1.5,
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            config.validate(),
+            Err(Error::InvalidNoveltyWeight(_))
+        ));
+    }
+
+    #[test]
+    fn obstacles() {
+        let config: Config = serde_json::from_str(
+            r#"{ "world_obstacles": [{ "center": [0.5, 0.5], "radius": 0.1 }] }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.world_obstacles,
+            [Obstacle::new(na::Point2::new(0.5, 0.5), 0.1).unwrap()]
+        );
+
+        let config = Config {
+            world_obstacles: vec![Obstacle {
+                center: na::Point2::new(0.5, 0.5),
+                radius: -1.0,
+            }],
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            config.validate(),
+            Err(Error::InvalidObstacleRadius(_))
+        ));
+    }
+
+    #[test]
+    fn no_animals() {
+        let config = Config {
+            world_animals: 0,
+            ..Default::default()
+        };
+
+        assert!(matches!(config.validate(), Err(Error::NoAnimals)));
+    }
+
+    #[test]
+    fn invalid_energy() {
+        let config = Config {
+            energy_initial: 0.0,
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            config.validate(),
+            Err(Error::InvalidInitialEnergy(_))
+        ));
+
+        let config = Config {
+            energy_speed_cost: -1.0,
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            config.validate(),
+            Err(Error::NegativeEnergy {
+                name: "speed cost",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn too_many_migrants() {
+        let config = Config {
+            world_animals: 4,
+            ga_migrants: 5,
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            config.validate(),
+            Err(Error::GeneticAlgorithm(ga::Error::PopulationTooSmall {
+                required: 5,
+                actual: 4
+            }))
+        ));
+    }
+}
@@ -0,0 +1,69 @@
+use thiserror::Error;
+
+use crate::*;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("brain's output activation must be symmetric, got {0:?}")]
+    AsymmetricOutputActivation(nn::Activation),
+
+    #[error("eye's field of view range must be positive, got {0}")]
+    InvalidFovRange(f32),
+
+    #[error("eye's field of view angle must be positive, got {0}")]
+    InvalidFovAngle(f32),
+
+    #[error("eye must have at least one cell")]
+    NoEyeCells,
+
+    #[error("minimum speed ({min}) must be positive and not greater than maximum speed ({max})")]
+    InvalidSpeedRange { min: f32, max: f32 },
+
+    #[error("speed acceleration must not be negative, got {0}")]
+    InvalidSpeedAccel(f32),
+
+    #[error("rotation acceleration must not be negative, got {0}")]
+    InvalidRotationAccel(f32),
+
+    #[error("world must contain at least one animal")]
+    NoAnimals,
+
+    #[error("food size must not be negative, got {0}")]
+    InvalidFoodSize(f32),
+
+    #[error("initial energy must be positive, got {0}")]
+    InvalidInitialEnergy(f32),
+
+    #[error("{name} must not be negative, got {value}")]
+    NegativeEnergy { name: &'static str, value: f32 },
+
+    #[error("obstacle radius must be positive, got {0}")]
+    InvalidObstacleRadius(f32),
+
+    #[error("novelty weight must be within 0.0..=1.0, got {0}")]
+    InvalidNoveltyWeight(f32),
+
+    #[error("brain has topology {actual:?}, but this config requires {expected:?}")]
+    BrainMismatch {
+        expected: Vec<nn::LayerTopology>,
+        actual: Vec<nn::LayerTopology>,
+    },
+
+    #[error("unsupported snapshot version {found} (expected {SNAPSHOT_VERSION})")]
+    UnsupportedSnapshotVersion { found: u32 },
+
+    #[error("snapshot's {0} doesn't match its config")]
+    SnapshotMismatch(&'static str),
+
+    #[error("malformed JSON snapshot")]
+    Json(#[from] serde_json::Error),
+
+    #[error("malformed binary snapshot")]
+    Binary(#[from] bincode::Error),
+
+    #[error(transparent)]
+    Network(#[from] nn::Error),
+
+    #[error(transparent)]
+    GeneticAlgorithm(#[from] ga::Error),
+}
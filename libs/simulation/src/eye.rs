@@ -1,49 +1,97 @@
-use std::f32::consts::{FRAC_PI_4, PI};
+use std::f32::consts::PI;
 
 use crate::*;
+use serde::{Deserialize, Serialize};
 
-const FOV_RANGE: f32 = 0.25;
-const FOV_ANGLE: f32 = PI + FRAC_PI_4;
-const CELLS: usize = 9;
-
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Eye {
     fov_range: f32,
     fov_angle: f32,
     cells: usize,
+    /// Whether the eye sees walls, too - see [`Eye::with_walls()`].
+    walls: bool,
 }
 
 impl Eye {
-    fn new(fov_range: f32, fov_angle: f32, cells: usize) -> Self {
-        assert!(fov_range > 0.0);
-        assert!(fov_angle > 0.0);
-        assert!(cells > 0);
+    fn new(fov_range: f32, fov_angle: f32, cells: usize) -> Result<Self, Error> {
+        if fov_range.is_nan() || fov_range <= 0.0 {
+            return Err(Error::InvalidFovRange(fov_range));
+        }
 
-        Self {
+        if fov_angle.is_nan() || fov_angle <= 0.0 {
+            return Err(Error::InvalidFovAngle(fov_angle));
+        }
+
+        if cells == 0 {
+            return Err(Error::NoEyeCells);
+        }
+
+        Ok(Self {
             fov_range,
             fov_angle,
             cells,
+            walls: false,
+        })
+    }
+
+    /// Adds a second set of cells that see how close the walls are, so that
+    /// birds can learn to avoid them.
+    pub fn with_walls(self) -> Self {
+        Self {
+            walls: true,
+            ..self
         }
     }
 
+    pub fn from_config(config: &Config) -> Result<Self, Error> {
+        let eye = Self::new(config.eye_fov_range, config.eye_fov_angle, config.eye_cells)?;
+
+        Ok(match config.world_boundary {
+            Boundary::Toroidal => eye,
+            Boundary::Walled => eye.with_walls(),
+        })
+    }
+
+    pub fn fov_range(&self) -> f32 {
+        self.fov_range
+    }
+
     pub fn cells(&self) -> usize {
         self.cells
     }
 
-    pub fn process_vision(
+    /// How many numbers `process_vision()` returns - that is: photoreceptors
+    /// for foods, followed by ones for walls (if there are any).
+    pub fn inputs(&self) -> usize {
+        if self.walls {
+            2 * self.cells
+        } else {
+            self.cells
+        }
+    }
+
+    pub fn process_vision<'a>(
         &self,
+        boundary: Boundary,
         position: na::Point2<f32>,
         rotation: na::Rotation2<f32>,
-        foods: &[Food],
+        foods: impl IntoIterator<Item = &'a Food>,
+        obstacles: &[Obstacle],
     ) -> Vec<f32> {
         let mut cells = vec![0.0; self.cells];
 
         for food in foods {
-            let vec = food.position - position;
+            let vec = boundary.displacement(position, food.position);
             let dist = vec.norm();
             if dist >= self.fov_range {
                 continue;
             }
+            if obstacles
+                .iter()
+                .any(|o| o.occludes(boundary, position, vec))
+            {
+                continue;
+            }
             let angle = na::Rotation2::rotation_between(&na::Vector2::x(), &vec).angle();
             let angle = angle - rotation.angle();
             let angle = na::wrap(angle, -PI, PI);
@@ -60,13 +108,43 @@ impl Eye {
             cells[cell] += energy;
         }
 
+        if self.walls {
+            cells.extend(self.process_walls(position, rotation));
+        }
+
         cells
     }
-}
 
-impl Default for Eye {
-    fn default() -> Self {
-        Self::new(FOV_RANGE, FOV_ANGLE, CELLS)
+    /// Casts a ray through the middle of each cell and returns how close
+    /// the wall it hits is - from 0.0 (out of range) up to 1.0 (right in
+    /// front of the bird's beak).
+    fn process_walls(
+        &self,
+        position: na::Point2<f32>,
+        rotation: na::Rotation2<f32>,
+    ) -> impl Iterator<Item = f32> + '_ {
+        let cell_angle = self.fov_angle / self.cells as f32;
+
+        (0..self.cells).map(move |cell| {
+            let angle = rotation.angle() - self.fov_angle / 2.0 + (cell as f32 + 0.5) * cell_angle;
+            let (sin, cos) = angle.sin_cos();
+
+            // Distance along the ray to the wall it's headed towards, on
+            // each axis separately
+            let along = |coord: f32, dir: f32| {
+                if dir > 0.0 {
+                    (1.0 - coord) / dir
+                } else if dir < 0.0 {
+                    -coord / dir
+                } else {
+                    f32::INFINITY
+                }
+            };
+
+            let dist = along(position.x, cos).min(along(position.y, sin));
+
+            ((self.fov_range - dist) / self.fov_range).max(0.0)
+        })
     }
 }
 
@@ -88,6 +166,7 @@ mod tests {
         foods: Vec<Food>,
         fov_range: f32,
         fov_angle: f32,
+        boundary: Boundary,
         x: f32,
         y: f32,
         rot: f32,
@@ -96,34 +175,38 @@ mod tests {
 
     impl TestCase {
         fn run(self) {
-            let eye = Eye::new(self.fov_range, self.fov_angle, TEST_EYE_CELLS);
+            let eye = Eye::new(self.fov_range, self.fov_angle, TEST_EYE_CELLS).unwrap();
 
             let actual_vision = eye.process_vision(
+                self.boundary,
                 na::Point2::new(self.x, self.y),
                 na::Rotation2::new(self.rot),
                 &self.foods,
+                &[],
             );
 
-            let actual_vision = actual_vision
-                .into_iter()
-                .map(|cell| {
-                    if cell >= 0.7 {
-                        "#"
-                    } else if cell >= 0.3 {
-                        "+"
-                    } else if cell > 0.0 {
-                        "."
-                    } else {
-                        " "
-                    }
-                })
-                .collect::<Vec<_>>()
-                .join("");
-
-            assert_eq!(actual_vision, self.expected_vision);
+            assert_eq!(render(&actual_vision), self.expected_vision);
         }
     }
 
+    fn render(vision: &[f32]) -> String {
+        vision
+            .iter()
+            .map(|&cell| {
+                if cell >= 0.7 {
+                    "#"
+                } else if cell >= 0.3 {
+                    "+"
+                } else if cell > 0.0 {
+                    "."
+                } else {
+                    " "
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("")
+    }
+
     #[test_case(1.0, "      +      ")] // Food is inside the FOV
     #[test_case(0.9, "      +      ")] // ditto
     #[test_case(0.8, "      +      ")] // ditto
@@ -139,6 +222,7 @@ mod tests {
             foods: vec![food(1.0, 0.5)],
             fov_range,
             fov_angle: FRAC_PI_2,
+            boundary: Boundary::Walled,
             x: 0.5,
             y: 0.5,
             rot: 0.0,
@@ -163,6 +247,7 @@ mod tests {
             foods: vec![food(0.5, 1.0)],
             fov_range: 1.0,
             fov_angle: 2.0 * PI,
+            boundary: Boundary::Walled,
             x: 0.5,
             y: 0.5,
             rot,
@@ -201,6 +286,7 @@ mod tests {
             foods: vec![food(1.0, 0.4), food(1.0, 0.6)],
             fov_range: 1.0,
             fov_angle: FRAC_PI_2,
+            boundary: Boundary::Walled,
             rot: 0.0,
             x,
             y,
@@ -230,6 +316,7 @@ mod tests {
                 food(1.0, 1.0),
             ],
             fov_range: 1.0,
+            boundary: Boundary::Walled,
             x: 0.5,
             y: 0.5,
             rot: 0.0,
@@ -237,4 +324,135 @@ mod tests {
             expected_vision,
         }.run()
     }
+
+    // Food lies just across the right edge of the world, 0.1 away:
+    #[test_case(Boundary::Toroidal, 0.0 * PI, "      +      ")] // right in front of us
+    #[test_case(Boundary::Toroidal, 0.5 * PI, "+            ")] // at the edge of sight
+    #[test_case(Boundary::Toroidal, 1.0 * PI, "             ")] // behind us
+    #[test_case(Boundary::Walled, 0.0 * PI, "             ")] // behind the wall
+    #[test_case(Boundary::Walled, 0.5 * PI, "             ")]
+    fn across_the_edge(boundary: Boundary, rot: f32, expected_vision: &'static str) {
+        TestCase {
+            foods: vec![food(0.05, 0.5)],
+            fov_range: 0.2,
+            fov_angle: PI,
+            boundary,
+            x: 0.95,
+            y: 0.5,
+            rot,
+            expected_vision,
+        }
+        .run()
+    }
+
+    // Foods lie in all four corners of the world, which - on a torus - are
+    // the same place, so the bird sees all of them at once
+    #[test_case(Boundary::Toroidal, 0.95, 0.95, "      #      ")]
+    #[test_case(Boundary::Toroidal, 0.05, 0.05, "             ")]
+    #[test_case(Boundary::Walled, 0.95, 0.95, "      +      ")]
+    fn in_the_corner(boundary: Boundary, x: f32, y: f32, expected_vision: &'static str) {
+        TestCase {
+            foods: vec![
+                food(0.0, 0.0),
+                food(0.0, 1.0),
+                food(1.0, 0.0),
+                food(1.0, 1.0),
+            ],
+            fov_range: 0.2,
+            fov_angle: FRAC_PI_2,
+            boundary,
+            x,
+            y,
+            rot: 0.25 * PI,
+            expected_vision,
+        }
+        .run()
+    }
+
+    // Bird sits 0.1 away from the right wall; foods are nowhere in sight,
+    // so only the wall cells (after the `|`) see anything
+    #[test_case(0.00 * PI, "             |+++++++++++++")] // Wall is right in front of us
+    #[test_case(0.25 * PI, "             |++++++...    ")] // It's to our right
+    #[test_case(0.50 * PI, "             |..           ")] // We fly along it
+    #[test_case(1.00 * PI, "             |             ")] // We fly away from it
+    #[test_case(1.75 * PI, "             |    ...++++++")] // It's to our left
+    fn walls(rot: f32, expected_vision: &'static str) {
+        let eye = Eye::new(0.2, FRAC_PI_2, TEST_EYE_CELLS)
+            .unwrap()
+            .with_walls();
+
+        let vision = eye.process_vision(
+            Boundary::Walled,
+            na::Point2::new(0.9, 0.5),
+            na::Rotation2::new(rot),
+            &[],
+            &[],
+        );
+
+        let (foods, walls) = vision.split_at(TEST_EYE_CELLS);
+        let actual_vision = format!("{}|{}", render(foods), render(walls));
+
+        assert_eq!(actual_vision, expected_vision);
+    }
+
+    #[test]
+    fn walls_in_corner() {
+        let eye = Eye::new(0.2, FRAC_PI_2, TEST_EYE_CELLS)
+            .unwrap()
+            .with_walls();
+
+        let vision = eye.process_vision(
+            Boundary::Walled,
+            na::Point2::new(0.95, 0.95),
+            na::Rotation2::new(0.25 * PI),
+            &[],
+            &[],
+        );
+
+        // Walls are closer to the sides of the eye than to its middle,
+        // which looks straight into the corner
+        assert_eq!(render(&vision[TEST_EYE_CELLS..]), "#####+++#####");
+    }
+
+    // Bird looks to the right, at two foods; rocks are halfway there
+    #[test_case(&[], "    +   +    ")]
+    #[test_case(&[(0.45, 0.02)], "        +    ")] // Rock hides the lower food
+    #[test_case(&[(0.55, 0.02)], "    +        ")] // Rock hides the upper food
+    #[test_case(&[(0.50, 0.02)], "    +   +    ")] // Rock is in between them
+    #[test_case(&[(0.50, 0.10)], "             ")] // Rock hides both foods
+    fn obstacles(rocks: &[(f32, f32)], expected_vision: &str) {
+        let eye = Eye::new(1.0, FRAC_PI_2, TEST_EYE_CELLS).unwrap();
+        let foods = [food(1.0, 0.4), food(1.0, 0.6)];
+
+        let rocks: Vec<_> = rocks
+            .iter()
+            .map(|&(y, radius)| Obstacle::new(na::Point2::new(0.75, y), radius).unwrap())
+            .collect();
+
+        let actual_vision = eye.process_vision(
+            Boundary::Walled,
+            na::Point2::new(0.5, 0.5),
+            na::Rotation2::new(0.0),
+            &foods,
+            &rocks,
+        );
+
+        assert_eq!(render(&actual_vision), expected_vision);
+    }
+
+    #[test_case(0.0, 1.0, 1, "eye's field of view range must be positive, got 0")]
+    #[test_case(-1.0, 1.0, 1, "eye's field of view range must be positive, got -1")]
+    #[test_case(
+        f32::NAN,
+        1.0,
+        1,
+        "eye's field of view range must be positive, got NaN"
+    )]
+    #[test_case(1.0, -1.0, 1, "eye's field of view angle must be positive, got -1")]
+    #[test_case(1.0, 1.0, 0, "eye must have at least one cell")]
+    fn invalid_parameters(fov_range: f32, fov_angle: f32, cells: usize, expected: &str) {
+        let err = Eye::new(fov_range, fov_angle, cells).unwrap_err();
+
+        assert_eq!(err.to_string(), expected);
+    }
 }
@@ -1,7 +1,8 @@
 use nalgebra as na;
-use rand::{RngCore, Rng};
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Food {
     pub(crate) position: na::Point2<f32>,
 }
@@ -0,0 +1,186 @@
+use crate::*;
+
+/// Uniform grid over the world, for finding things near a point without
+/// looking at everything.
+///
+/// The world is a unit square that can wrap around (see `Boundary`), so
+/// cells on one edge neighbour cells on the opposite edge - in a walled
+/// world this merely yields a few extra candidates.
+///
+/// Queries return candidates only - everything within the radius, plus
+/// some things around it - so callers still have to check the distance on
+/// their own.
+///
+/// The grid knows nothing about what it indexes - each step the simulation
+/// builds one over foods (for vision) and one over birds (for collisions),
+/// see `Simulation::advance()`.
+#[derive(Debug)]
+pub(crate) struct Grid {
+    /// Number of cells along each axis.
+    size: usize,
+    /// Indices of items in each cell, row by row.
+    cells: Vec<Vec<usize>>,
+}
+
+impl Grid {
+    /// Indexes given positions, identifying each by its index.
+    ///
+    /// The grid has roughly one cell per item, which keeps both the number
+    /// of cells and the number of items per cell low, whatever the radius
+    /// of queries is going to be.
+    pub(crate) fn new(positions: impl ExactSizeIterator<Item = na::Point2<f32>>) -> Self {
+        let size = ((positions.len() as f32).sqrt() as usize).max(1);
+
+        let mut grid = Self {
+            size,
+            cells: vec![Vec::new(); size * size],
+        };
+
+        for (idx, position) in positions.enumerate() {
+            grid.insert(idx, position);
+        }
+
+        grid
+    }
+
+    pub(crate) fn insert(&mut self, idx: usize, position: na::Point2<f32>) {
+        let cell = self.cell(position);
+
+        self.cells[cell].push(idx);
+    }
+
+    /// Moves item from `from` to `to`; `from` has to be where the item was
+    /// inserted at.
+    pub(crate) fn relocate(&mut self, idx: usize, from: na::Point2<f32>, to: na::Point2<f32>) {
+        let cell = self.cell(from);
+        let cell = &mut self.cells[cell];
+
+        if let Some(pos) = cell.iter().position(|&other| other == idx) {
+            cell.swap_remove(pos);
+        }
+
+        self.insert(idx, to);
+    }
+
+    /// Returns (in ascending order) indices of items that might lie within
+    /// `radius` of `center`, wrapping around the edges of the world.
+    pub(crate) fn query(&self, center: na::Point2<f32>, radius: f32) -> Vec<usize> {
+        let columns = self.span(center.x, radius);
+        let rows = self.span(center.y, radius);
+
+        let mut found: Vec<_> = rows
+            .flat_map(|row| {
+                columns
+                    .clone()
+                    .flat_map(move |column| &self.cells[row * self.size + column])
+            })
+            .copied()
+            .collect();
+
+        // Callers expect items in the same order as they'd get iterating
+        // over all of them, so that e.g. vision sums energies the same way
+        found.sort_unstable();
+        found
+    }
+
+    /// Returns cells along a single axis that overlap `value ± radius`.
+    fn span(&self, value: f32, radius: f32) -> impl Iterator<Item = usize> + Clone {
+        let size = self.size as isize;
+        let mut from = ((value - radius) * self.size as f32).floor() as isize;
+        let mut to = ((value + radius) * self.size as f32).floor() as isize;
+
+        if to - from + 1 >= size {
+            from = 0;
+            to = size - 1;
+        }
+
+        (from..=to).map(move |cell| cell.rem_euclid(size) as usize)
+    }
+
+    fn cell(&self, position: na::Point2<f32>) -> usize {
+        let coord = |value: f32| {
+            let cell = (value.rem_euclid(1.0) * self.size as f32) as usize;
+
+            // `rem_euclid()` can round up to exactly 1.0
+            cell.min(self.size - 1)
+        };
+
+        coord(position.y) * self.size + coord(position.x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(positions: &[(f32, f32)]) -> Grid {
+        Grid::new(positions.iter().map(|&(x, y)| na::Point2::new(x, y)))
+    }
+
+    #[test]
+    fn query() {
+        let grid = grid(&[
+            (0.1, 0.1),
+            (0.5, 0.5),
+            (0.9, 0.9),
+            (0.55, 0.45),
+            (0.2, 0.8),
+            (0.8, 0.2),
+            (0.6, 0.6),
+            (0.3, 0.3),
+            (0.4, 0.7),
+        ]);
+
+        assert_eq!(grid.size, 3);
+
+        // Cells are a third of the world wide, so this looks at the middle
+        // one only
+        assert_eq!(grid.query(na::Point2::new(0.5, 0.5), 0.1), [1, 3, 6]);
+    }
+
+    #[test]
+    fn query_wraps_around() {
+        let grid = grid(&[
+            (0.05, 0.5),
+            (0.5, 0.5),
+            (0.95, 0.5),
+            (0.5, 0.05),
+            (0.5, 0.95),
+            (0.3, 0.3),
+            (0.7, 0.7),
+            (0.3, 0.7),
+            (0.7, 0.3),
+            (0.5, 0.3),
+            (0.5, 0.7),
+            (0.3, 0.5),
+            (0.7, 0.5),
+            (0.4, 0.4),
+            (0.6, 0.6),
+            (0.4, 0.6),
+        ]);
+
+        assert_eq!(grid.size, 4);
+
+        // Looking to the right of the right edge finds things on the left
+        // edge, and vice versa
+        assert_eq!(grid.query(na::Point2::new(0.99, 0.5), 0.05), [0, 2]);
+        assert_eq!(grid.query(na::Point2::new(0.01, 0.5), 0.05), [0, 2]);
+    }
+
+    #[test]
+    fn large_radius_finds_everything() {
+        let grid = grid(&[(0.1, 0.1), (0.5, 0.5), (0.9, 0.9), (0.2, 0.8)]);
+
+        assert_eq!(grid.query(na::Point2::new(0.5, 0.5), 2.0), [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn relocate() {
+        let mut grid = grid(&[(0.1, 0.1), (0.9, 0.9), (0.1, 0.9), (0.9, 0.1)]);
+
+        grid.relocate(0, na::Point2::new(0.1, 0.1), na::Point2::new(0.9, 0.9));
+
+        assert!(grid.query(na::Point2::new(0.1, 0.1), 0.1).is_empty());
+        assert_eq!(grid.query(na::Point2::new(0.9, 0.9), 0.05), [0, 1]);
+    }
+}
@@ -0,0 +1,145 @@
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// The best birds ever seen, across all generations - so that a good
+/// brain doesn't get lost just because its generation has ended.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HallOfFame {
+    capacity: usize,
+    /// Sorted from the best one.
+    members: Vec<Champion>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Champion {
+    /// Generation the bird lived in.
+    pub generation: usize,
+
+    /// How much the bird ate; birds get into the hall of fame by this,
+    /// not by their fitness, which also depends on energy and novelty.
+    pub satiation: f32,
+
+    /// Bird's brain, see `Animal::as_chromosome()`.
+    pub chromosome: ga::Chromosome,
+}
+
+impl HallOfFame {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            members: Vec::new(),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Checks whether the hall of fame (e.g. one loaded from a snapshot)
+    /// is the one given config would create.
+    pub(crate) fn check(&self, config: &Config) -> Result<(), Error> {
+        if self.capacity != config.ga_hall_of_fame_size || self.members.len() > self.capacity {
+            return Err(Error::SnapshotMismatch("hall of fame"));
+        }
+
+        Ok(())
+    }
+
+    pub fn members(&self) -> &[Champion] {
+        &self.members
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Considers birds of given generation for the hall of fame.
+    ///
+    /// Birds have to be strictly better than the worst member to get in,
+    /// so on ties the older ones stay.
+    pub(crate) fn record(&mut self, generation: usize, population: &[AnimalIndividual]) {
+        use ga::Individual;
+
+        if self.capacity == 0 {
+            return;
+        }
+
+        for individual in population {
+            let satiation = individual.satiation();
+
+            let full = self.members.len() == self.capacity;
+
+            if full
+                && self
+                    .members
+                    .last()
+                    .is_some_and(|worst| satiation <= worst.satiation)
+            {
+                continue;
+            }
+
+            let idx = self
+                .members
+                .partition_point(|member| member.satiation >= satiation);
+
+            self.members.insert(
+                idx,
+                Champion {
+                    generation,
+                    satiation,
+                    chromosome: individual.chromosome().clone(),
+                },
+            );
+
+            self.members.truncate(self.capacity);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn population(satiations: &[f32]) -> Vec<AnimalIndividual> {
+        satiations
+            .iter()
+            .map(|&satiation| {
+                AnimalIndividual::new(satiation, std::iter::once(satiation).collect())
+            })
+            .collect()
+    }
+
+    fn satiations(hall_of_fame: &HallOfFame) -> Vec<(usize, f32)> {
+        hall_of_fame
+            .members()
+            .iter()
+            .map(|member| (member.generation, member.satiation))
+            .collect()
+    }
+
+    #[test]
+    fn keeps_the_best() {
+        let mut hall_of_fame = HallOfFame::new(3);
+
+        hall_of_fame.record(0, &population(&[1.0, 5.0, 2.0, 0.0]));
+        assert_eq!(satiations(&hall_of_fame), [(0, 5.0), (0, 2.0), (0, 1.0)]);
+
+        hall_of_fame.record(1, &population(&[3.0, 2.0, 1.0]));
+        assert_eq!(satiations(&hall_of_fame), [(0, 5.0), (1, 3.0), (0, 2.0)]);
+
+        assert_eq!(hall_of_fame.members()[1].chromosome[0], 3.0);
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let mut hall_of_fame = HallOfFame::new(0);
+
+        hall_of_fame.record(0, &population(&[1.0, 5.0]));
+        assert!(hall_of_fame.is_empty());
+    }
+}
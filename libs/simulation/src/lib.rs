@@ -1,153 +1,965 @@
 mod animal;
 mod animal_individual;
+mod archipelago;
+mod boundary;
+mod config;
+mod error;
 mod eye;
 mod food;
+mod grid;
+mod hall_of_fame;
+mod obstacle;
+mod population;
+mod selection;
+mod snapshot;
+mod statistics;
+mod streams;
 mod world;
 
-pub use self::{animal::*, eye::*, food::*, world::*};
+pub use self::{
+    animal::*, archipelago::*, boundary::*, config::*, error::*, eye::*, food::*, hall_of_fame::*,
+    obstacle::*, population::*, selection::*, snapshot::*, statistics::*, world::*,
+};
 use animal_individual::AnimalIndividual;
+use grid::Grid;
 pub use lib_genetic_algorithm as ga;
 pub use lib_neural_network as nn;
 use nalgebra as na;
-use rand::{Rng, RngCore};
-
-use std::f32::consts::FRAC_PI_2;
-const SPEED_MIN: f32 = 0.001;
-const SPEED_MAX: f32 = 0.005;
-const SPEED_ACCEL: f32 = 0.2;
-const ROTATION_ACCEL: f32 = FRAC_PI_2;
-const GENERATION_LENGTH: usize = 2500;
+use rand::RngCore;
+use streams::Streams;
 
 pub struct Simulation {
+    config: Config,
+    streams: Streams,
     world: World,
-    ga: ga::GeneticAlgorithm<ga::RouletteWheelSelection>,
+    ga: ga::GeneticAlgorithm<Selection>,
+    nsga: Option<ga::Nsga2>,
+    novelty: ga::NoveltyArchive,
+    hall_of_fame: HallOfFame,
     age: usize,
+    generation: usize,
+    statistics: Option<GenerationStatistics>,
 }
 
 impl Simulation {
-    pub fn random(rng: &mut dyn RngCore) -> Self {
-        let world = World::random(rng);
-
-        let ga = ga::GeneticAlgorithm::new(
-            ga::RouletteWheelSelection::default(),
-            ga::UniformCrossover::default(),
-            ga::GaussianMutation::new(0.01, 0.3),
-            // ---------------------- ^--^ -^-^
-            // | Chosen with a bit of experimentation.
-            // |
-            // | Higher values can make the simulation more chaotic,
-            // | which - a bit counterintuitively - might allow for
-            // | it to discover *better* solutions; but the trade-off
-            // | is that higher values might also cause current, good
-            // | enough solutions to be discarded.
-            // ---
-        );
+    /// Creates a simulation seeded from given generator.
+    ///
+    /// The generator is used only once, to pick a seed - see
+    /// [`Simulation::from_seed()`].
+    pub fn random(config: Config, rng: &mut dyn RngCore) -> Result<Self, Error> {
+        Self::from_seed(rng.next_u64(), config)
+    }
+
+    /// Creates a simulation that owns its randomness; two simulations
+    /// created from the same seed and config evolve identically.
+    pub fn from_seed(seed: u64, config: Config) -> Result<Self, Error> {
+        config.validate()?;
+
+        let mut streams = Streams::from_seed(seed);
+        let world = World::random(&config, &mut streams.world)?;
+        let ga = Self::genetic_algorithm(&config)?;
+        let nsga = Self::nsga2(&config)?;
+        let novelty = config.novelty_archive()?;
+        let hall_of_fame = HallOfFame::new(config.ga_hall_of_fame_size);
 
-        Self { world, ga, age: 0 }
+        Ok(Self {
+            config,
+            streams,
+            world,
+            ga,
+            nsga,
+            novelty,
+            hall_of_fame,
+            age: 0,
+            generation: 0,
+            statistics: None,
+        })
+    }
+
+    pub(crate) fn genetic_algorithm(
+        config: &Config,
+    ) -> Result<ga::GeneticAlgorithm<Selection>, Error> {
+        Ok(ga::GeneticAlgorithm::new(
+            config.ga_selection,
+            ga::UniformCrossover::new(),
+            ga::UniformMutation::new(config.ga_mut_chance, config.ga_mut_coeff)?,
+        ))
+    }
+
+    /// Returns NSGA-II when the config asks for multi-objective evolution.
+    pub(crate) fn nsga2(config: &Config) -> Result<Option<ga::Nsga2>, Error> {
+        if !config.ga_multi_objective {
+            return Ok(None);
+        }
+
+        Ok(Some(ga::Nsga2::new(
+            ga::UniformCrossover::new(),
+            ga::UniformMutation::new(config.ga_mut_chance, config.ga_mut_coeff)?,
+        )))
+    }
+
+    /// Creates a simulation whose animals carry given, already evolved,
+    /// brains.
+    pub fn from_population(seed: u64, population: Population) -> Result<Self, Error> {
+        if population.brains.is_empty() {
+            return Err(Error::NoAnimals);
+        }
+
+        let mut simulation = Self::from_seed(seed, population.config)?;
+        let config = &simulation.config;
+        let rng = &mut simulation.streams.world;
+
+        simulation.world.animals = population
+            .brains
+            .into_iter()
+            .map(|brain| Animal::from_brain(config, brain, rng))
+            .collect::<Result<_, _>>()?;
+
+        Ok(simulation)
+    }
+
+    pub fn population(&self) -> Population {
+        Population {
+            config: self.config.clone(),
+            brains: self
+                .world
+                .animals
+                .iter()
+                .map(|animal| animal.brain.clone())
+                .collect(),
+        }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
     }
 
     pub fn world(&self) -> &World {
         &self.world
     }
 
-    pub fn step(&mut self, rng: &mut dyn RngCore) {
-        self.process_collisions(rng);
-        self.process_brains();
+    /// Index of the generation that's currently being simulated.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Behaviours remembered by novelty search; stays empty unless
+    /// `Config::ga_novelty_weight` is positive.
+    pub fn novelty_archive(&self) -> &ga::NoveltyArchive {
+        &self.novelty
+    }
+
+    /// The best birds of all generations so far.
+    pub fn hall_of_fame(&self) -> &HallOfFame {
+        &self.hall_of_fame
+    }
+
+    /// Brings back up to `count` best birds from the hall of fame,
+    /// replacing the birds that ate the least so far in the current
+    /// generation; returns how many birds got replaced.
+    pub fn reinject_hall_of_fame(&mut self, count: usize) -> Result<usize, Error> {
+        let animals = &mut self.world.animals;
+        let count = count.min(self.hall_of_fame.len()).min(animals.len());
+
+        let mut hungriest: Vec<_> = (0..animals.len()).collect();
+        hungriest.sort_by_key(|&idx| animals[idx].satiation);
+
+        let champions = self.hall_of_fame.members().iter().take(count);
+
+        for (&idx, champion) in hungriest.iter().zip(champions) {
+            animals[idx] = Animal::from_chromosome(
+                &self.config,
+                champion.chromosome.clone(),
+                &mut self.streams.world,
+            )?;
+        }
+
+        Ok(count)
+    }
+
+    /// Statistics of the most recently evolved generation, if any.
+    pub fn statistics(&self) -> Option<&GenerationStatistics> {
+        self.statistics.as_ref()
+    }
+
+    /// Performs a single step; when it ends the current generation,
+    /// returns statistics of that generation.
+    pub fn step(&mut self) -> Result<Option<GenerationStatistics>, Error> {
+        self.advance()?;
+
+        if self.generation_finished() {
+            self.evolve().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Fast-forwards until the end of the current generation.
+    pub fn train(&mut self) -> Result<GenerationStatistics, Error> {
+        loop {
+            if let Some(statistics) = self.step()? {
+                return Ok(statistics);
+            }
+        }
+    }
+
+    /// Moves the world one step forward, without evolving.
+    pub(crate) fn advance(&mut self) -> Result<(), Error> {
+        // Rebuilding indices from scratch each step is way cheaper than
+        // comparing every bird with every food
+        let mut foods = Grid::new(self.world.foods.iter().map(|food| food.position));
+        let animals = Grid::new(self.world.animals.iter().map(|animal| animal.position));
+
+        self.process_collisions(&mut foods, &animals);
+        self.process_brains(&foods)?;
         self.process_movements();
         self.age += 1;
 
-        if self.age > GENERATION_LENGTH {
-            self.evolve(rng);
-        }
+        Ok(())
     }
 
-    fn evolve(&mut self, rng: &mut dyn RngCore) {
-        self.age = 0;
+    /// Generation ends after given number of steps, or sooner if all of
+    /// the birds have starved to death.
+    pub(crate) fn generation_finished(&self) -> bool {
+        self.age > self.config.sim_generation_length
+            || self.world.animals.iter().all(|animal| !animal.is_alive())
+    }
 
+    fn evolve(&mut self) -> Result<GenerationStatistics, Error> {
         // Step 1: Prepare birdies to be sent into the genetic algorithm
-        let current_population: Vec<_> = self
-            .world
-            .animals
-            .iter()
-            .map(AnimalIndividual::from_animal)
-            .collect();
+        let current_population = self.individuals();
 
         // Step 2: Evolve birdies
-        let evolved_population = self.ga.evolve(rng, &current_population);
+        let rng = &mut self.streams.evolution;
+
+        let evolved_population = match &self.nsga {
+            Some(nsga) => nsga.evolve_at(rng, &current_population, self.generation)?,
+            None => self
+                .ga
+                .evolve_at(rng, &current_population, self.generation)?,
+        };
+
+        self.repopulate(&current_population, evolved_population)
+    }
+
+    /// Returns individuals of the generation that just ended; when novelty
+    /// search is on, also scores (and archives) their behaviours.
+    pub(crate) fn individuals(&mut self) -> Vec<AnimalIndividual> {
+        let animals = &self.world.animals;
+        let mut individuals: Vec<_> = animals.iter().map(AnimalIndividual::from_animal).collect();
+
+        let weight = self.config.ga_novelty_weight;
+
+        if weight > 0.0 {
+            let behaviours: Vec<_> = animals
+                .iter()
+                .map(|animal| animal.behaviour(self.age))
+                .collect();
+
+            let novelties = self.novelty.evaluate(&behaviours);
+
+            for (individual, novelty) in individuals.iter_mut().zip(novelties) {
+                individual.blend_novelty(novelty, weight);
+            }
+        }
+
+        individuals
+    }
+
+    /// Replaces birds of the generation that just ended (`current`) with
+    /// the evolved ones, and starts a new generation.
+    pub(crate) fn repopulate(
+        &mut self,
+        current: &[AnimalIndividual],
+        evolved: Vec<AnimalIndividual>,
+    ) -> Result<GenerationStatistics, Error> {
+        let statistics = GenerationStatistics::new(self.generation, current)?;
+        self.hall_of_fame.record(self.generation, current);
+
+        let rng = &mut self.streams.evolution;
+
+        self.age = 0;
 
         // Step 3: Bring birdies back from the genetic algorithm
-        self.world.animals = evolved_population
+        self.world.animals = evolved
             .into_iter()
-            .map(|individual| individual.into_animal(rng))
-            .collect();
+            .map(|individual| individual.into_animal(&self.config, rng))
+            .collect::<Result<_, _>>()?;
 
         // Step 4: Restart foods
         //
         // (this is not strictly necessary, but it allows to easily spot
         // when the evolution happens - so it's more of a UI thing.)
         for food in &mut self.world.foods {
-            food.position = rng.gen();
+            food.position = obstacle::free_position(
+                &mut self.streams.food,
+                self.config.world_boundary,
+                &self.world.obstacles,
+            );
         }
-    }
 
-    fn process_brains(&mut self) {
-        for animal in &mut self.world.animals {
-            let vision =
-                animal
-                    .eye
-                    .process_vision(animal.position, animal.rotation, &self.world.foods);
+        self.generation += 1;
+        self.statistics = Some(statistics.clone());
 
-            let response = animal.brain.propagate(vision);
+        Ok(statistics)
+    }
 
-            // ---
-            // | Limits number to given range.
-            // -------------------- v---v
-            let speed = response[0].clamp(-SPEED_ACCEL, SPEED_ACCEL);
+    fn process_brains(&mut self, grid: &Grid) -> Result<(), Error> {
+        #[cfg(feature = "parallel")]
+        use rayon::prelude::*;
 
-            let rotation = response[1].clamp(-ROTATION_ACCEL, ROTATION_ACCEL);
+        let config = &self.config;
+        let foods = &self.world.foods;
+        let obstacles = &self.world.obstacles;
 
-            // Our speed & rotation here are *relative* - that is: when
-            // they are equal to zero, what the brain says is "keep
-            // flying as you are now", not "stop flying".
-            //
-            // Both values being relative is crucial, because our bird's
-            // brain doesn't know its own speed and rotation*, meaning
-            // that it fundamentally cannot return absolute values.
-            //
-            // * they'd have to be provided as separate inputs to the
-            //   neural network, which would make the evolution process
-            //   waaay longer, if even possible.
+        // Brains don't affect each other (nor use any randomness), so they
+        // can safely think all at once
+        #[cfg(feature = "parallel")]
+        let animals = self.world.animals.par_iter_mut();
 
-            animal.speed = (animal.speed + speed).clamp(SPEED_MIN, SPEED_MAX);
+        #[cfg(not(feature = "parallel"))]
+        let mut animals = self.world.animals.iter_mut();
 
-            animal.rotation = na::Rotation2::new(animal.rotation.angle() + rotation);
+        animals.try_for_each(|animal| Self::process_brain(config, foods, obstacles, grid, animal))
+    }
 
-            // (btw, there is no need for ROTATION_MIN or ROTATION_MAX,
-            // because rotation automatically wraps from 2*PI back to 0 -
-            // we've already witnessed that when we were testing eyes,
-            // inside `mod different_rotations { ... }`.)
+    fn process_brain(
+        config: &Config,
+        foods: &[Food],
+        obstacles: &[Obstacle],
+        grid: &Grid,
+        animal: &mut Animal,
+    ) -> Result<(), Error> {
+        if !animal.is_alive() {
+            return Ok(());
         }
+
+        let visible = grid
+            .query(animal.position, animal.eye.fov_range())
+            .into_iter()
+            .map(|idx| &foods[idx]);
+
+        let vision = animal.eye.process_vision(
+            config.world_boundary,
+            animal.position,
+            animal.rotation,
+            visible,
+            obstacles,
+        );
+
+        let response = animal.brain.propagate(vision)?;
+
+        // ---
+        // | Limits number to given range.
+        // -------------------- v---v
+        let speed = response[0].clamp(-config.sim_speed_accel, config.sim_speed_accel);
+
+        let rotation = response[1].clamp(-config.sim_rotation_accel, config.sim_rotation_accel);
+
+        // Our speed & rotation here are *relative* - that is: when
+        // they are equal to zero, what the brain says is "keep
+        // flying as you are now", not "stop flying".
+        //
+        // Both values being relative is crucial, because our bird's
+        // brain doesn't know its own speed and rotation*, meaning
+        // that it fundamentally cannot return absolute values.
+        //
+        // * they'd have to be provided as separate inputs to the
+        //   neural network, which would make the evolution process
+        //   waaay longer, if even possible.
+
+        animal.speed = (animal.speed + speed).clamp(config.sim_speed_min, config.sim_speed_max);
+
+        animal.rotation = na::Rotation2::new(animal.rotation.angle() + rotation);
+        animal.metabolize(config, rotation);
+
+        // (btw, there is no need for `sim_rotation_min` or `_max`,
+        // because rotation automatically wraps from 2*PI back to 0 -
+        // we've already witnessed that when we were testing eyes,
+        // inside `mod different_rotations { ... }`.)
+
+        Ok(())
     }
 
     fn process_movements(&mut self) {
         for animal in &mut self.world.animals {
+            // Dead birds stay where they've died
+            if !animal.is_alive() {
+                animal.position_sum += animal.position.coords;
+                continue;
+            }
+
             animal.position += animal.rotation * na::Vector2::new(animal.speed, 0.0);
+            animal.distance += animal.speed;
+
+            match self.config.world_boundary {
+                Boundary::Toroidal => {
+                    animal.position = Boundary::Toroidal.confine(animal.position);
+                }
+
+                Boundary::Walled => self
+                    .config
+                    .world_wall_collision
+                    .collide(&mut animal.position, &mut animal.rotation),
+            }
+
+            for obstacle in &self.world.obstacles {
+                animal.position = obstacle.push_out(self.config.world_boundary, animal.position);
+            }
 
-            animal.position.x = na::wrap(animal.position.x, 0.0, 1.0);
-            animal.position.y = na::wrap(animal.position.y, 0.0, 1.0);
+            animal.position_sum += animal.position.coords;
         }
     }
 
-    fn process_collisions(&mut self, rng: &mut dyn RngCore) {
-        for animal in &mut self.world.animals {
-            for food in &mut self.world.foods {
-                let distance = na::distance(&animal.position, &food.position);
+    /// Lets birds eat foods they've flown into; eaten foods reappear
+    /// somewhere else (and get moved in `foods` accordingly).
+    fn process_collisions(&mut self, foods: &mut Grid, animals: &Grid) {
+        let boundary = self.config.world_boundary;
+        let food_size = self.config.food_size;
 
-                if distance <= 0.01 {
-                    animal.satiation += 1;
-                    food.position = rng.gen();
-                }
+        for (idx, food) in self.world.foods.iter_mut().enumerate() {
+            // When more birds reach the same food, the first one eats it
+            let eater = animals
+                .query(food.position, food_size)
+                .into_iter()
+                .find(|&eater| {
+                    let animal = &self.world.animals[eater];
+
+                    animal.is_alive()
+                        && boundary.distance(animal.position, food.position) <= food_size
+                });
+
+            let Some(eater) = eater else {
+                continue;
+            };
+
+            let position =
+                obstacle::free_position(&mut self.streams.food, boundary, &self.world.obstacles);
+
+            foods.relocate(idx, food.position, position);
+
+            let animal = &mut self.world.animals[eater];
+            animal.satiation += 1;
+            animal.energy += self.config.energy_per_food;
+            food.position = position;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    /// FNV-1a over the bit patterns of everything that changes during a
+    /// run; unlike `DefaultHasher`, it's guaranteed to stay the same
+    /// between Rust releases.
+    fn hash_world(world: &World) -> u64 {
+        let mut hash = 0xcbf29ce484222325_u64;
+
+        let mut write = |value: u32| {
+            for byte in value.to_le_bytes() {
+                hash ^= byte as u64;
+                hash = hash.wrapping_mul(0x100000001b3);
+            }
+        };
+
+        for animal in world.animals() {
+            write(animal.position.x.to_bits());
+            write(animal.position.y.to_bits());
+            write(animal.rotation.angle().to_bits());
+            write(animal.speed.to_bits());
+            write(animal.satiation as u32);
+
+            for weight in animal.brain.weights() {
+                write(weight.to_bits());
             }
         }
+
+        for food in world.foods() {
+            write(food.position.x.to_bits());
+            write(food.position.y.to_bits());
+        }
+
+        hash
+    }
+
+    fn config() -> Config {
+        Config {
+            sim_generation_length: 100,
+            world_animals: 10,
+            world_foods: 50,
+            ..Default::default()
+        }
+    }
+
+    fn run(seed: u64, generations: usize) -> Simulation {
+        let mut simulation = Simulation::from_seed(seed, config()).unwrap();
+
+        for _ in 0..generations {
+            simulation.train().unwrap();
+        }
+
+        simulation
+    }
+
+    #[test]
+    fn evolves_after_generation_length() {
+        let simulation = run(0, 1);
+
+        assert_eq!(simulation.age, 0);
+        assert_eq!(simulation.world().animals().len(), config().world_animals);
+        assert!(simulation
+            .world()
+            .animals()
+            .iter()
+            .all(|a| a.satiation == 0));
+    }
+
+    #[test]
+    fn reports_statistics() {
+        let mut simulation = Simulation::from_seed(0, config()).unwrap();
+        assert!(simulation.statistics().is_none());
+
+        let statistics = simulation.train().unwrap();
+
+        assert_eq!(statistics.generation, 0);
+        assert_eq!(simulation.generation(), 1);
+        assert_eq!(simulation.statistics(), Some(&statistics));
+        assert!(statistics.min_satiation <= statistics.median_satiation);
+        assert!(statistics.median_satiation <= statistics.max_satiation);
+    }
+
+    #[test]
+    fn same_seed_gives_same_world() {
+        assert_eq!(
+            hash_world(run(42, 3).world()),
+            hash_world(run(42, 3).world())
+        );
+    }
+
+    #[test]
+    fn different_seeds_give_different_worlds() {
+        assert_ne!(hash_world(run(1, 1).world()), hash_world(run(2, 1).world()));
+    }
+
+    /// If this test fails, something made the simulation nondeterministic
+    /// or changed its behavior; when the change was intentional, update
+    /// the expected hash.
+    #[test]
+    fn regression() {
+        assert_eq!(hash_world(run(42, 5).world()), 8356785249442496051);
+    }
+
+    #[test]
+    fn population_round_trip() {
+        let simulation = run(7, 1);
+        let population = simulation.population();
+        let restored = Simulation::from_population(7, population.clone()).unwrap();
+
+        assert_eq!(restored.population(), population);
+    }
+
+    #[test]
+    fn population_with_mismatched_brains() {
+        let mut population = run(7, 0).population();
+        population.config.eye_cells = 5;
+
+        let err = Simulation::from_population(7, population).err().unwrap();
+
+        match err {
+            Error::BrainMismatch { expected, actual } => {
+                assert_eq!(expected[0].neurons, 5);
+                assert_eq!(actual[0].neurons, 9);
+            }
+            err => panic!("unexpected error: {err}"),
+        }
+    }
+
+    #[test_case(Selection::RouletteWheel)]
+    #[test_case(Selection::Tournament { size: 2 })]
+    #[test_case(Selection::Rank)]
+    #[test_case(Selection::StochasticUniversalSampling)]
+    #[test_case(Selection::Truncation { ratio: 0.5 })]
+    fn selection(selection: Selection) {
+        let mut simulation = Simulation::from_seed(
+            0,
+            Config {
+                ga_selection: selection,
+                ..config()
+            },
+        )
+        .unwrap();
+
+        for generation in 0..3 {
+            assert_eq!(simulation.train().unwrap().generation, generation);
+        }
+    }
+
+    #[test]
+    fn multi_objective() {
+        let config = Config {
+            ga_multi_objective: true,
+            ..config()
+        };
+
+        let mut simulation = Simulation::from_seed(3, config.clone()).unwrap();
+
+        for _ in 0..config.sim_generation_length {
+            simulation.step().unwrap();
+        }
+
+        assert!(simulation
+            .world()
+            .animals()
+            .iter()
+            .all(|animal| animal.distance() > 0.0));
+
+        for _ in 0..3 {
+            simulation.train().unwrap();
+        }
+
+        assert_eq!(simulation.world().animals().len(), config.world_animals);
+        assert_ne!(
+            hash_world(simulation.world()),
+            hash_world(run(3, 4).world())
+        );
+    }
+
+    #[test]
+    fn novelty_search() {
+        let config = Config {
+            ga_novelty_weight: 0.5,
+            ..config()
+        };
+
+        let mut simulation = Simulation::from_seed(5, config.clone()).unwrap();
+
+        for _ in 0..3 {
+            simulation.train().unwrap();
+        }
+
+        assert_eq!(
+            simulation.novelty_archive().len(),
+            3 * config.ga_novelty_archived
+        );
+
+        assert_ne!(
+            hash_world(simulation.world()),
+            hash_world(run(5, 3).world())
+        );
+    }
+
+    #[test]
+    fn novelty_search_without_food() {
+        // Nobody is ever going to eat anything, so satiation alone would
+        // give the genetic algorithm nothing to go by
+        let config = Config {
+            food_size: 0.0,
+            ga_novelty_weight: 1.0,
+            ..config()
+        };
+
+        let mut simulation = Simulation::from_seed(5, config).unwrap();
+        let statistics = simulation.train().unwrap();
+
+        assert_eq!(statistics.food_eaten, 0);
+        assert!(simulation.train().is_ok());
+    }
+
+    #[test]
+    fn hall_of_fame() {
+        let mut simulation = Simulation::from_seed(11, config()).unwrap();
+        let mut best = 0.0_f32;
+
+        for _ in 0..3 {
+            best = best.max(simulation.train().unwrap().max_satiation);
+        }
+
+        let members = simulation.hall_of_fame().members();
+
+        assert_eq!(members.len(), config().ga_hall_of_fame_size);
+        assert_eq!(members[0].satiation, best);
+
+        assert!(members
+            .windows(2)
+            .all(|pair| pair[0].satiation >= pair[1].satiation));
+    }
+
+    #[test]
+    fn reinject_hall_of_fame() {
+        let mut simulation = run(11, 2);
+        let champions: Vec<_> = simulation.hall_of_fame().members()[..2].to_vec();
+
+        assert_eq!(simulation.reinject_hall_of_fame(2).unwrap(), 2);
+
+        for champion in champions {
+            let weights: Vec<_> = champion.chromosome.into_iter().collect();
+
+            assert!(simulation
+                .world()
+                .animals()
+                .iter()
+                .any(|animal| animal.brain.weights() == weights));
+        }
+
+        // Can't bring back more birds than there are
+        assert_eq!(
+            simulation.reinject_hall_of_fame(1000).unwrap(),
+            config().world_animals
+        );
+    }
+
+    #[test_case(Boundary::Toroidal, 1)]
+    #[test_case(Boundary::Walled, 0)]
+    fn eating_across_the_edge(boundary: Boundary, expected_satiation: usize) {
+        let mut simulation = Simulation::from_seed(
+            0,
+            Config {
+                food_size: 0.02,
+                world_animals: 2,
+                world_foods: 1,
+                world_boundary: boundary,
+                ..config()
+            },
+        )
+        .unwrap();
+
+        simulation.world.animals[0].position = na::Point2::new(0.995, 0.5);
+        simulation.world.animals[1].position = na::Point2::new(0.5, 0.0);
+        simulation.world.foods[0].position = na::Point2::new(0.005, 0.5);
+        simulation.advance().unwrap();
+
+        assert_eq!(
+            simulation.world().animals()[0].satiation,
+            expected_satiation
+        );
+    }
+
+    #[test_case(WallCollision::Stop, 1.0, 0.0)]
+    #[test_case(WallCollision::Bounce, 0.996, std::f32::consts::PI)]
+    fn flying_into_a_wall(collision: WallCollision, expected_x: f32, expected_rotation: f32) {
+        let mut simulation = Simulation::from_seed(
+            0,
+            Config {
+                world_boundary: Boundary::Walled,
+                world_wall_collision: collision,
+                ..config()
+            },
+        )
+        .unwrap();
+
+        let animal = &mut simulation.world.animals[0];
+        animal.position = na::Point2::new(0.999, 0.5);
+        animal.rotation = na::Rotation2::new(0.0);
+        animal.speed = 0.005;
+
+        simulation.process_movements();
+
+        let animal = &simulation.world().animals()[0];
+
+        approx::assert_relative_eq!(animal.position.x, expected_x, epsilon = 1e-6);
+        approx::assert_relative_eq!(animal.position.y, 0.5);
+        approx::assert_relative_eq!(animal.rotation.angle().abs(), expected_rotation);
+    }
+
+    #[test]
+    fn walled_world() {
+        let config = Config {
+            world_boundary: Boundary::Walled,
+            ..config()
+        };
+
+        let mut simulation = Simulation::from_seed(0, config.clone()).unwrap();
+
+        for _ in 0..2 {
+            simulation.train().unwrap();
+        }
+
+        for animal in simulation.world().animals() {
+            assert_eq!(animal.brain.topology()[0].neurons, 2 * config.eye_cells);
+
+            assert!((0.0..=1.0).contains(&animal.position.x));
+            assert!((0.0..=1.0).contains(&animal.position.y));
+        }
+    }
+
+    #[test]
+    fn obstacles() {
+        let rock = Obstacle::new(na::Point2::new(0.5, 0.5), 0.2).unwrap();
+
+        let mut simulation = Simulation::from_seed(
+            0,
+            Config {
+                world_obstacles: vec![rock.clone()],
+                ..config()
+            },
+        )
+        .unwrap();
+
+        // Bird flies straight into the rock...
+        let animal = &mut simulation.world.animals[0];
+        animal.position = na::Point2::new(0.25, 0.5);
+        animal.rotation = na::Rotation2::new(0.0);
+        animal.speed = 0.1;
+
+        simulation.process_movements();
+
+        // ... and ends up stuck at its edge
+        approx::assert_relative_eq!(
+            simulation.world().animals()[0].position,
+            na::Point2::new(0.3, 0.5),
+            epsilon = 1e-6
+        );
+
+        for _ in 0..2 {
+            simulation.train().unwrap();
+        }
+
+        for food in simulation.world().foods() {
+            assert!(!rock.contains(Boundary::Toroidal, food.position));
+        }
+    }
+
+    #[test]
+    fn animals_spawn_outside_of_obstacles() {
+        let rock = Obstacle::new(na::Point2::new(0.5, 0.5), 0.4).unwrap();
+
+        let mut simulation = Simulation::from_seed(
+            0,
+            Config {
+                world_animals: 100,
+                world_obstacles: vec![rock.clone()],
+                ..config()
+            },
+        )
+        .unwrap();
+
+        let assert_outside = |simulation: &Simulation| {
+            for animal in simulation.world().animals() {
+                assert!(!rock.contains(Boundary::Toroidal, animal.position));
+            }
+        };
+
+        assert_outside(&simulation);
+
+        // `train()` returns right after repopulating, before anybody moves
+        simulation.train().unwrap();
+        assert_outside(&simulation);
+
+        let simulation = Simulation::from_population(0, simulation.population()).unwrap();
+        assert_outside(&simulation);
+    }
+
+    #[test]
+    fn starvation() {
+        let mut simulation = Simulation::from_seed(
+            0,
+            Config {
+                energy_initial: 1.0,
+                energy_per_food: 0.0,
+                energy_metabolism: 0.25,
+                ..config()
+            },
+        )
+        .unwrap();
+
+        for _ in 0..4 {
+            assert!(!simulation.generation_finished());
+            simulation.advance().unwrap();
+        }
+
+        // Everyone's starved, so the generation ends early...
+        assert!(simulation.world().animals().iter().all(|a| !a.is_alive()));
+        assert!(simulation.generation_finished());
+
+        // ... and dead birds don't move anymore
+        let positions: Vec<_> = simulation
+            .world()
+            .animals()
+            .iter()
+            .map(|a| a.position)
+            .collect();
+        simulation.advance().unwrap();
+
+        for (animal, position) in simulation.world().animals().iter().zip(positions) {
+            assert_eq!(animal.position, position);
+        }
+
+        simulation.train().unwrap();
+
+        assert_eq!(simulation.generation(), 1);
+        assert!(simulation.world().animals().iter().all(|a| a.is_alive()));
+    }
+
+    #[test_case(Selection::RouletteWheel)]
+    #[test_case(Selection::StochasticUniversalSampling)]
+    fn everybody_starves_without_eating(selection: Selection) {
+        let mut simulation = Simulation::from_seed(
+            0,
+            Config {
+                energy_metabolism: 0.25,
+                world_foods: 0,
+                ga_selection: selection,
+                ..config()
+            },
+        )
+        .unwrap();
+
+        // Nobody's eaten anything, so everybody's fitness is zero - which
+        // must not stop the evolution
+        for generation in 0..2 {
+            let statistics = simulation.train().unwrap();
+
+            assert_eq!(statistics.generation, generation);
+            assert_eq!(statistics.food_eaten, 0);
+        }
+
+        assert_eq!(simulation.generation(), 2);
+    }
+
+    #[test]
+    fn eating_restores_energy() {
+        let mut simulation = Simulation::from_seed(
+            0,
+            Config {
+                energy_initial: 1.0,
+                energy_per_food: 0.5,
+                energy_metabolism: 0.25,
+                ..config()
+            },
+        )
+        .unwrap();
+
+        let position = simulation.world.animals[0].position;
+        simulation.world.foods[0].position = position;
+        simulation.advance().unwrap();
+
+        assert_eq!(simulation.world().animals()[0].satiation, 1);
+        assert_eq!(simulation.world().animals()[0].energy, 1.25);
+        assert_eq!(simulation.world().animals()[0].energy_spent, 0.25);
+    }
+
+    #[test]
+    fn food_goes_to_the_first_living_bird() {
+        let mut simulation = Simulation::from_seed(0, config()).unwrap();
+        let position = na::Point2::new(0.5, 0.5);
+
+        for animal in &mut simulation.world.animals[..3] {
+            animal.position = position;
+        }
+
+        simulation.world.animals[0].energy = 0.0;
+        simulation.world.foods[0].position = position;
+
+        simulation.advance().unwrap();
+
+        let satiations: Vec<_> = simulation.world().animals()[..3]
+            .iter()
+            .map(|animal| animal.satiation)
+            .collect();
+
+        assert_eq!(satiations, [0, 1, 0]);
+        assert_ne!(simulation.world().foods()[0].position, position);
     }
 }
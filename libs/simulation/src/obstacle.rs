@@ -0,0 +1,173 @@
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// A round, static thing (say: a rock) that birds can neither fly nor see
+/// through.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Obstacle {
+    pub(crate) center: na::Point2<f32>,
+    pub(crate) radius: f32,
+}
+
+impl Obstacle {
+    pub fn new(center: na::Point2<f32>, radius: f32) -> Result<Self, Error> {
+        if !radius.is_finite() || radius <= 0.0 {
+            return Err(Error::InvalidObstacleRadius(radius));
+        }
+
+        Ok(Self { center, radius })
+    }
+
+    pub fn center(&self) -> na::Point2<f32> {
+        self.center
+    }
+
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    pub(crate) fn contains(&self, boundary: Boundary, point: na::Point2<f32>) -> bool {
+        boundary.distance(self.center, point) < self.radius
+    }
+
+    /// Whether the obstacle stands in the way of looking from `from` along
+    /// `vec` (e.g. at a food).
+    pub(crate) fn occludes(
+        &self,
+        boundary: Boundary,
+        from: na::Point2<f32>,
+        vec: na::Vector2<f32>,
+    ) -> bool {
+        let center = boundary.displacement(from, self.center);
+        let len = vec.norm_squared();
+
+        // Point of the line of sight that's the closest to our center
+        let t = if len > 0.0 {
+            (center.dot(&vec) / len).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+
+        (center - vec * t).norm() < self.radius
+    }
+
+    /// Moves a point that's inside of the obstacle onto its edge, so that
+    /// birds slide along obstacles instead of flying through them.
+    pub(crate) fn push_out(&self, boundary: Boundary, point: na::Point2<f32>) -> na::Point2<f32> {
+        let vec = boundary.displacement(self.center, point);
+        let dist = vec.norm();
+
+        if dist >= self.radius {
+            return point;
+        }
+
+        let dir = if dist > 0.0 {
+            vec / dist
+        } else {
+            na::Vector2::x()
+        };
+
+        boundary.confine(self.center + dir * self.radius)
+    }
+}
+
+/// Returns a random position that's not inside of any obstacle.
+///
+/// If obstacles cover (almost) the entire world, gives up after a couple
+/// of tries and returns whatever it got last.
+pub(crate) fn free_position(
+    rng: &mut dyn RngCore,
+    boundary: Boundary,
+    obstacles: &[Obstacle],
+) -> na::Point2<f32> {
+    let mut position = rng.gen();
+
+    for _ in 0..100 {
+        if !obstacles.iter().any(|o| o.contains(boundary, position)) {
+            break;
+        }
+
+        position = rng.gen();
+    }
+
+    position
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn obstacle(x: f32, y: f32, radius: f32) -> Obstacle {
+        Obstacle::new(na::Point2::new(x, y), radius).unwrap()
+    }
+
+    #[test_case((0.7, 0.5), true)] // Straight through the middle
+    #[test_case((0.7, 0.58), true)] // Through the edge
+    #[test_case((0.7, 0.7), false)] // Next to it
+    #[test_case((0.45, 0.5), false)] // Food is in front of it
+    #[test_case((0.3, 0.5), false)] // Food is behind us
+    fn occludes(food: (f32, f32), expected: bool) {
+        let from = na::Point2::new(0.4, 0.5);
+        let food = na::Point2::new(food.0, food.1);
+
+        let actual = obstacle(0.55, 0.5, 0.05).occludes(Boundary::Walled, from, food - from);
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn occludes_across_the_edge() {
+        let obstacle = obstacle(0.02, 0.5, 0.05);
+        let from = na::Point2::new(0.9, 0.5);
+        let vec = na::Vector2::new(0.2, 0.0);
+
+        assert!(obstacle.occludes(Boundary::Toroidal, from, vec));
+        assert!(!obstacle.occludes(Boundary::Walled, from, vec));
+    }
+
+    #[test]
+    fn push_out() {
+        let obstacle = obstacle(0.5, 0.5, 0.1);
+
+        approx::assert_relative_eq!(
+            obstacle.push_out(Boundary::Walled, na::Point2::new(0.45, 0.5)),
+            na::Point2::new(0.4, 0.5)
+        );
+
+        approx::assert_relative_eq!(
+            obstacle.push_out(Boundary::Walled, na::Point2::new(0.5, 0.58)),
+            na::Point2::new(0.5, 0.6)
+        );
+
+        // Points outside are left alone
+        approx::assert_relative_eq!(
+            obstacle.push_out(Boundary::Walled, na::Point2::new(0.3, 0.3)),
+            na::Point2::new(0.3, 0.3)
+        );
+    }
+
+    #[test]
+    fn free_position() {
+        use rand::SeedableRng;
+
+        let mut rng = rand_chacha::ChaCha8Rng::from_seed(Default::default());
+        let obstacles = [obstacle(0.5, 0.5, 0.4)];
+
+        for _ in 0..100 {
+            let position = super::free_position(&mut rng, Boundary::Walled, &obstacles);
+
+            assert!(!obstacles[0].contains(Boundary::Walled, position));
+        }
+    }
+
+    #[test]
+    fn invalid_radius() {
+        assert!(matches!(
+            Obstacle::new(na::Point2::new(0.5, 0.5), 0.0),
+            Err(Error::InvalidObstacleRadius(_))
+        ));
+    }
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{nn, Config};
+
+/// Brains of every animal in a world, together with the config they were
+/// evolved under.
+///
+/// This is what offline training produces - feeding it back into
+/// [`crate::Simulation::from_population()`] continues where the training
+/// left off (minus positions of animals and foods, which get randomized).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Population {
+    pub config: Config,
+
+    pub brains: Vec<nn::Network>,
+}
@@ -0,0 +1,96 @@
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// How parents get picked for the next generation; each variant stands for
+/// the `ga` selection method of the same name.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Selection {
+    /// See `ga::RouletteWheelSelection`.
+    #[default]
+    RouletteWheel,
+
+    /// See `ga::TournamentSelection`.
+    Tournament { size: usize },
+
+    /// See `ga::RankSelection`.
+    Rank,
+
+    /// See `ga::StochasticUniversalSampling`.
+    StochasticUniversalSampling,
+
+    /// See `ga::TruncationSelection`.
+    Truncation { ratio: f32 },
+}
+
+impl Selection {
+    /// Checks the method's parameters - e.g. that a tournament is not
+    /// empty.
+    pub(crate) fn validate(&self) -> Result<(), ga::Error> {
+        match *self {
+            Self::Tournament { size } => ga::TournamentSelection::new(size).map(drop),
+            Self::Truncation { ratio } => ga::TruncationSelection::new(ratio).map(drop),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl ga::SelectionMethod for Selection {
+    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> Result<&'a I, ga::Error>
+    where
+        I: ga::Individual,
+    {
+        match *self {
+            Self::RouletteWheel => ga::RouletteWheelSelection::new().select(rng, population),
+            Self::Tournament { size } => {
+                ga::TournamentSelection::new(size)?.select(rng, population)
+            }
+            Self::Rank => ga::RankSelection::new().select(rng, population),
+            Self::StochasticUniversalSampling => {
+                ga::StochasticUniversalSampling::new().select(rng, population)
+            }
+            Self::Truncation { ratio } => {
+                ga::TruncationSelection::new(ratio)?.select(rng, population)
+            }
+        }
+    }
+
+    fn select_many<'a, I>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &'a [I],
+        count: usize,
+    ) -> Result<Vec<&'a I>, ga::Error>
+    where
+        I: ga::Individual,
+    {
+        match *self {
+            // The only method that looks at the whole batch
+            Self::StochasticUniversalSampling => {
+                ga::StochasticUniversalSampling::new().select_many(rng, population, count)
+            }
+            _ => (0..count).map(|_| self.select(rng, population)).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(r#""roulette_wheel""#, Selection::RouletteWheel)]
+    #[test_case(r#"{ "tournament": { "size": 3 } }"#, Selection::Tournament { size: 3 })]
+    #[test_case(r#"{ "truncation": { "ratio": 0.5 } }"#, Selection::Truncation { ratio: 0.5 })]
+    fn deserialize(json: &str, expected: Selection) {
+        assert_eq!(serde_json::from_str::<Selection>(json).unwrap(), expected);
+    }
+
+    #[test_case(Selection::Tournament { size: 0 }, ga::Error::InvalidTournamentSize(0))]
+    #[test_case(Selection::Truncation { ratio: 1.5 }, ga::Error::InvalidTruncationRatio(1.5))]
+    fn invalid(selection: Selection, expected: ga::Error) {
+        assert_eq!(selection.validate().unwrap_err(), expected);
+    }
+}
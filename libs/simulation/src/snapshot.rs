@@ -0,0 +1,277 @@
+//! Saving and loading of the entire simulation state.
+//!
+//! Snapshots come in two flavours - JSON, which is easy to inspect and
+//! hand-edit, and bincode, which is a few times smaller and faster to
+//! (de)serialize. Both start with [`SNAPSHOT_VERSION`], so that a
+//! snapshot written by an incompatible version of the simulation gets
+//! rejected instead of being misinterpreted.
+
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// Version of the snapshot format; bump it whenever the serialized shape
+/// of the simulation changes.
+pub const SNAPSHOT_VERSION: u32 = 12;
+
+#[derive(Serialize)]
+struct SnapshotRef<'a> {
+    version: u32,
+    config: &'a Config,
+    streams: &'a Streams,
+    world: &'a World,
+    novelty: &'a ga::NoveltyArchive,
+    hall_of_fame: &'a HallOfFame,
+    age: usize,
+    generation: usize,
+    statistics: &'a Option<GenerationStatistics>,
+}
+
+#[derive(Deserialize)]
+struct Snapshot {
+    version: u32,
+    config: Config,
+    streams: Streams,
+    world: World,
+    novelty: ga::NoveltyArchive,
+    hall_of_fame: HallOfFame,
+    age: usize,
+    generation: usize,
+    statistics: Option<GenerationStatistics>,
+}
+
+#[derive(Deserialize)]
+struct Version {
+    version: u32,
+}
+
+impl Simulation {
+    pub fn to_json(&self) -> Result<String, Error> {
+        Ok(serde_json::to_string(&self.snapshot())?)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let Version { version } = serde_json::from_str(json)?;
+        check_version(version)?;
+
+        Self::restore(serde_json::from_str(json)?)
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        Ok(bincode::serialize(&self.snapshot())?)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        // Bincode encodes fields one after another, so the version can be
+        // read without knowing anything about the rest of the snapshot
+        check_version(bincode::deserialize(bytes)?)?;
+
+        Self::restore(bincode::deserialize(bytes)?)
+    }
+
+    fn snapshot(&self) -> SnapshotRef<'_> {
+        SnapshotRef {
+            version: SNAPSHOT_VERSION,
+            config: &self.config,
+            streams: &self.streams,
+            world: &self.world,
+            novelty: &self.novelty,
+            hall_of_fame: &self.hall_of_fame,
+            age: self.age,
+            generation: self.generation,
+            statistics: &self.statistics,
+        }
+    }
+
+    fn restore(snapshot: Snapshot) -> Result<Self, Error> {
+        let Snapshot {
+            version,
+            config,
+            streams,
+            world,
+            novelty,
+            hall_of_fame,
+            age,
+            generation,
+            statistics,
+        } = snapshot;
+
+        check_version(version)?;
+        config.validate()?;
+        world.check(&config)?;
+        hall_of_fame.check(&config)?;
+
+        Ok(Self {
+            ga: Self::genetic_algorithm(&config)?,
+            nsga: Self::nsga2(&config)?,
+            config,
+            streams,
+            world,
+            novelty,
+            hall_of_fame,
+            age,
+            generation,
+            statistics,
+        })
+    }
+}
+
+fn check_version(version: u32) -> Result<(), Error> {
+    if version == SNAPSHOT_VERSION {
+        Ok(())
+    } else {
+        Err(Error::UnsupportedSnapshotVersion { found: version })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn simulation() -> Simulation {
+        let mut simulation = Simulation::from_seed(
+            13,
+            Config {
+                sim_generation_length: 50,
+                food_size: 0.05,
+                world_animals: 8,
+                world_foods: 30,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        simulation.train().unwrap();
+
+        for _ in 0..20 {
+            simulation.step().unwrap();
+        }
+
+        simulation
+    }
+
+    /// Restored simulation must not only look the same, but also continue
+    /// exactly as the original one would.
+    fn assert_resumes_identically(mut original: Simulation, mut restored: Simulation) {
+        assert_eq!(restored.to_json().unwrap(), original.to_json().unwrap());
+
+        original.train().unwrap();
+        restored.train().unwrap();
+
+        assert_eq!(restored.to_json().unwrap(), original.to_json().unwrap());
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let original = simulation();
+        let restored = Simulation::from_json(&original.to_json().unwrap()).unwrap();
+
+        assert_eq!(restored.age, original.age);
+        assert_eq!(restored.generation(), 1);
+        assert_eq!(restored.statistics(), original.statistics());
+        assert_resumes_identically(original, restored);
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        let original = simulation();
+        let bytes = original.to_bytes().unwrap();
+
+        assert!(bytes.len() < original.to_json().unwrap().len());
+
+        let restored = Simulation::from_bytes(&bytes).unwrap();
+        assert_resumes_identically(original, restored);
+    }
+
+    #[test]
+    fn rejects_unknown_json_version() {
+        let json = simulation().to_json().unwrap();
+        let json = json.replacen(r#""version":12"#, r#""version":999"#, 1);
+
+        assert!(matches!(
+            Simulation::from_json(&json),
+            Err(Error::UnsupportedSnapshotVersion { found: 999 })
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_binary_version() {
+        let mut bytes = simulation().to_bytes().unwrap();
+        bytes[0] = 1;
+
+        assert!(matches!(
+            Simulation::from_bytes(&bytes),
+            Err(Error::UnsupportedSnapshotVersion { found: 1 })
+        ));
+    }
+
+    #[test]
+    fn rejects_world_not_matching_config() {
+        let mut json: serde_json::Value =
+            serde_json::from_str(&simulation().to_json().unwrap()).unwrap();
+
+        json["config"]["eye_fov_range"] = 0.5.into();
+
+        assert!(matches!(
+            Simulation::from_json(&json.to_string()),
+            Err(Error::SnapshotMismatch("eye"))
+        ));
+
+        let mut json: serde_json::Value =
+            serde_json::from_str(&simulation().to_json().unwrap()).unwrap();
+
+        // Eyes match, but brains are still made for nine cells
+        json["config"]["eye_cells"] = 5.into();
+
+        for animal in json["world"]["animals"].as_array_mut().unwrap() {
+            animal["eye"]["cells"] = 5.into();
+        }
+
+        assert!(matches!(
+            Simulation::from_json(&json.to_string()),
+            Err(Error::BrainMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_hall_of_fame_not_matching_config() {
+        let mut json: serde_json::Value =
+            serde_json::from_str(&simulation().to_json().unwrap()).unwrap();
+
+        json["config"]["ga_hall_of_fame_size"] = 3.into();
+
+        assert!(matches!(
+            Simulation::from_json(&json.to_string()),
+            Err(Error::SnapshotMismatch("hall of fame"))
+        ));
+    }
+
+    #[test]
+    fn rejects_obstacles_not_matching_config() {
+        let mut json: serde_json::Value =
+            serde_json::from_str(&simulation().to_json().unwrap()).unwrap();
+
+        json["world"]["obstacles"] = serde_json::json!([{ "center": [0.5, 0.5], "radius": 0.1 }]);
+
+        assert!(matches!(
+            Simulation::from_json(&json.to_string()),
+            Err(Error::SnapshotMismatch("obstacles"))
+        ));
+    }
+
+    #[test]
+    fn rejects_brain_with_wrong_weight_count() {
+        let mut json: serde_json::Value =
+            serde_json::from_str(&simulation().to_json().unwrap()).unwrap();
+
+        json["world"]["animals"][0]["brain"]["layers"][0]["weights"][0]
+            .as_array_mut()
+            .unwrap()
+            .pop();
+
+        assert!(matches!(
+            Simulation::from_json(&json.to_string()),
+            Err(Error::Json(_))
+        ));
+    }
+}
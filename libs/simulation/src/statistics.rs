@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// Summary of how a single generation went, gathered right before the
+/// population gets evolved.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GenerationStatistics {
+    /// Index of the generation these statistics describe, starting at 0.
+    pub generation: usize,
+    pub min_satiation: f32,
+    pub max_satiation: f32,
+    pub mean_satiation: f32,
+    pub median_satiation: f32,
+    pub stddev_satiation: f32,
+
+    /// How many foods all animals ate together.
+    pub food_eaten: usize,
+
+    /// How different the brains are from each other, see
+    /// `ga::diversity()`.
+    #[serde(default)]
+    pub diversity: f32,
+}
+
+impl GenerationStatistics {
+    /// Summarizes how much birds ate - unlike their fitness, that's not
+    /// affected by novelty.
+    pub(crate) fn new(generation: usize, population: &[AnimalIndividual]) -> Result<Self, Error> {
+        let satiations = population.iter().map(|i| i.satiation());
+        let statistics = ga::Statistics::from_fitnesses(generation, satiations.clone())?;
+
+        Ok(Self {
+            generation,
+            min_satiation: statistics.min_fitness,
+            max_satiation: statistics.max_fitness,
+            mean_satiation: statistics.mean_fitness,
+            median_satiation: statistics.median_fitness,
+            stddev_satiation: statistics.stddev_fitness,
+            food_eaten: satiations.sum::<f32>() as usize,
+            diversity: ga::diversity(population),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarizes_satiation() {
+        let mut population: Vec<_> = [4.0, 1.0, 7.0]
+            .into_iter()
+            .map(|satiation| AnimalIndividual::new(satiation, std::iter::empty().collect()))
+            .collect();
+
+        // Novelty changes fitness, but not what the birds ate
+        population[2].blend_novelty(0.0, 1.0);
+
+        let actual = GenerationStatistics::new(3, &population).unwrap();
+
+        assert_eq!(actual.generation, 3);
+        approx::assert_relative_eq!(actual.min_satiation, 1.0);
+        approx::assert_relative_eq!(actual.max_satiation, 7.0);
+        approx::assert_relative_eq!(actual.median_satiation, 4.0);
+        assert_eq!(actual.food_eaten, 12);
+    }
+
+    #[test]
+    fn empty_population() {
+        assert!(matches!(
+            GenerationStatistics::new(0, &[]),
+            Err(Error::GeneticAlgorithm(ga::Error::EmptyPopulation))
+        ));
+    }
+}
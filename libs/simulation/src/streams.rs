@@ -0,0 +1,38 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+/// Independent random number streams derived from a single seed.
+///
+/// Each part of the simulation draws from its own stream, so e.g. eating
+/// one more food (and thus respawning it) doesn't shift the numbers the
+/// genetic algorithm gets - which keeps runs comparable when tweaking a
+/// single parameter.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct Streams {
+    pub(crate) world: ChaCha8Rng,
+    pub(crate) food: ChaCha8Rng,
+    pub(crate) evolution: ChaCha8Rng,
+}
+
+impl Streams {
+    pub(crate) fn from_seed(seed: u64) -> Self {
+        Self {
+            world: Self::stream(seed, 0),
+            food: Self::stream(seed, 1),
+            evolution: Self::stream(seed, 2),
+        }
+    }
+
+    /// Stream of an `Archipelago` - used to seed its islands, and then to
+    /// migrate and evolve them.
+    pub(crate) fn archipelago(seed: u64) -> ChaCha8Rng {
+        Self::stream(seed, 3)
+    }
+
+    fn stream(seed: u64, stream: u64) -> ChaCha8Rng {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        rng.set_stream(stream);
+        rng
+    }
+}
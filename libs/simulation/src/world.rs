@@ -1,17 +1,27 @@
+use crate::{animal::Animal, food::Food, obstacle, Config, Error, Obstacle};
 use rand::RngCore;
-use crate::{animal::Animal, food::Food};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct World {
     pub(crate) animals: Vec<Animal>,
     pub(crate) foods: Vec<Food>,
+    pub(crate) obstacles: Vec<Obstacle>,
 }
 
 impl World {
-    pub fn random(rng: &mut dyn RngCore) -> Self {
-        let animals = (0..40).map(|_| Animal::random(rng)).collect();
+    pub fn random(config: &Config, rng: &mut dyn RngCore) -> Result<Self, Error> {
+        let animals = (0..config.world_animals)
+            .map(|_| Animal::random(config, rng))
+            .collect::<Result<_, _>>()?;
 
-        let foods = (0..60).map(|_| Food::random(rng)).collect();
+        let obstacles = config.world_obstacles.clone();
+
+        let foods = (0..config.world_foods)
+            .map(|_| Food {
+                position: obstacle::free_position(rng, config.world_boundary, &obstacles),
+            })
+            .collect();
 
         // ^ Our algorithm allows for animals and foods to overlap, so
         // | it's hardly ideal - but good enough for our purposes.
@@ -22,7 +32,25 @@ impl World {
         // | https://en.wikipedia.org/wiki/Supersampling
         // ---
 
-        Self { animals, foods }
+        Ok(Self {
+            animals,
+            foods,
+            obstacles,
+        })
+    }
+
+    /// Checks whether the world (e.g. one loaded from a snapshot) could've
+    /// been created from given config.
+    pub(crate) fn check(&self, config: &Config) -> Result<(), Error> {
+        if self.obstacles != config.world_obstacles {
+            return Err(Error::SnapshotMismatch("obstacles"));
+        }
+
+        for animal in &self.animals {
+            animal.check(config)?;
+        }
+
+        Ok(())
     }
 
     pub fn animals(&self) -> &[Animal] {
@@ -32,5 +60,9 @@ impl World {
     pub fn foods(&self) -> &[Food] {
         &self.foods
     }
+
+    pub fn obstacles(&self) -> &[Obstacle] {
+        &self.obstacles
+    }
 }
 
@@ -20,7 +20,7 @@ const ctxt = viewport.getContext('2d');
 // Rest of the code follows without any changes
 ctxt.fillStyle = 'rgb(0, 0, 0)';
 
-function drawTriangle(ctxt, x, y, size, rotation) {
+function drawTriangle(ctxt, x, y, size, rotation, color = 'rgb(255, 255, 255)') {
   ctxt.beginPath();
   const initial = [
     x + Math.cos(rotation) * size * 1.5,
@@ -42,16 +42,16 @@ function drawTriangle(ctxt, x, y, size, rotation) {
   ctxt.lineTo(...initial);
 
   ctxt.stroke();
-  ctxt.fillStyle = 'rgb(255, 255, 255)'; // A nice white color
+  ctxt.fillStyle = color;
   ctxt.fill();
 }
 
-function drawCircle(ctxt, x, y, radius) {
+function drawCircle(ctxt, x, y, radius, color = 'rgb(0, 255, 128)') {
   ctxt.beginPath();
 
   ctxt.arc(x, y, radius, 0, 2.0 * Math.PI);
 
-  ctxt.fillStyle = 'rgb(0, 255, 128)';
+  ctxt.fillStyle = color;
   ctxt.fill();
 };
 
@@ -61,6 +61,15 @@ function redraw() {
   simulation.step();
 
   const world = simulation.world();
+  for (const obstacle of world.obstacles) {
+    drawCircle(
+      ctxt,
+      obstacle.x * viewportWidth,
+      obstacle.y * viewportHeight,
+      obstacle.radius * viewportWidth,
+      'rgb(128, 128, 128)',
+    );
+  }
   for (const food of world.foods) {
     drawCircle(
       ctxt,
@@ -76,6 +85,7 @@ function redraw() {
       animal.y * viewportHeight,
       0.01 * viewportWidth,
       animal.rotation,
+      animal.alive ? 'rgb(255, 255, 255)' : 'rgb(96, 96, 96)',
     );
   }
 